@@ -1,6 +1,45 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    time::{Duration, Instant},
+};
+
+use chrono::{Local, NaiveDate, Utc};
+use ratatui::{
+    layout::{Position, Rect},
+    widgets::ListState,
+};
 use uuid::Uuid;
 
-use crate::models::{Board, Priority, Task};
+use crate::{
+    handlers::{
+        external_editor::TaskDocument,
+        notifier::{NoopNotifier, Notifier},
+        storage::{self, BoardSummary},
+    },
+    models::{
+        Board, Priority, Task,
+        board::HiddenTask,
+        column::WipPolicy,
+        dependency::DependencyGraph,
+        event::TaskEvent,
+        due_date::{DueStatus, parse_due_date, to_due_datetime},
+        filter::{DueFilter, SavedView, TaskFilter},
+        history::{Command, History},
+        metrics::{self, Metrics},
+        search,
+        task::{ChecklistItem, HiddenState},
+    },
+    ui::text_editor::TextEditor,
+};
+
+/// How long the board must go without changes before `tick` writes it out.
+/// Edits made in quick succession are coalesced into a single save.
+const AUTOSAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Longest wait between autosave attempts while saving keeps failing. The
+/// wait doubles from `AUTOSAVE_DELAY` with every failure up to this.
+const MAX_SAVE_BACKOFF: Duration = Duration::from_secs(60);
 
 #[derive(Clone)]
 pub enum InputMode {
@@ -8,32 +47,166 @@ pub enum InputMode {
     Editing,
     AddingTask,
     MovingTask,
+    BoardPicker,
+    NamingBoard,
+    ManagingColumns,
+    NamingColumn,
+    SettingWipLimit,
+    DeletingColumn,
+    Searching,
+    Filtering,
+    NamingView,
+    RenamingLabel,
+    Archive,
+    TaskDetail,
+    Metrics,
 }
 
 #[derive(Clone)]
 pub struct EditState {
-    pub title: String,
-    pub description: String,
+    pub title: TextEditor,
+    pub description: TextEditor,
+    pub due_date: TextEditor,
     pub priority: Priority,
+    /// Comma separated label names.
+    pub labels: TextEditor,
+    pub checklist: Vec<ChecklistItem>,
+    /// Text of the checklist item being typed.
+    pub checklist_input: TextEditor,
+    pub selected_item: usize,
+    /// Ids of the tasks this one waits for.
+    pub blockers: Vec<Uuid>,
+    /// Title search for a blocker to add.
+    pub blocker_query: TextEditor,
+    pub selected_blocker: usize,
+    /// Highlighted entry of `App::blocker_candidates`.
+    pub selected_candidate: usize,
     pub selected_field: EditField,
+    /// Text saved in the external editor that could not be parsed. The next
+    /// external edit reopens it instead of the fields, so nothing typed is
+    /// lost.
+    pub rejected_document: Option<String>,
 }
 
 #[derive(Clone)]
 pub enum EditField {
     Title,
     Description,
+    DueDate,
     Priority,
+    Labels,
+    Checklist,
+    Blockers,
 }
 
 impl Default for EditState {
     fn default() -> Self {
         Self {
-            title: String::new(),
-            description: String::new(),
+            title: TextEditor::default(),
+            description: TextEditor::multiline(""),
+            due_date: TextEditor::default(),
             priority: Priority::Medium,
+            labels: TextEditor::default(),
+            checklist: Vec::new(),
+            checklist_input: TextEditor::default(),
+            selected_item: 0,
+            blockers: Vec::new(),
+            blocker_query: TextEditor::default(),
+            selected_blocker: 0,
+            selected_candidate: 0,
             selected_field: EditField::Title,
+            rejected_document: None,
+        }
+    }
+}
+
+impl EditState {
+    /// Interprets the due date input relative to the local calendar day.
+    pub fn parsed_due_date(&self) -> Result<Option<NaiveDate>, String> {
+        parse_due_date(self.due_date.text(), Local::now().date_naive())
+    }
+
+    /// Distinct, non-empty label names from the labels input.
+    pub fn label_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for name in self.labels.text().split(',').map(str::trim) {
+            if !name.is_empty() && !names.iter().any(|seen| seen.eq_ignore_ascii_case(name)) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Appends the typed checklist item.
+    pub fn add_checklist_item(&mut self) {
+        let text = self.checklist_input.text().trim();
+        if text.is_empty() {
+            return;
+        }
+        self.checklist.push(ChecklistItem::new(text.to_string()));
+        self.checklist_input.clear();
+        self.selected_item = self.checklist.len() - 1;
+    }
+
+    pub fn toggle_checklist_item(&mut self) {
+        if let Some(item) = self.checklist.get_mut(self.selected_item) {
+            item.done = !item.done;
         }
     }
+
+    pub fn delete_checklist_item(&mut self) {
+        if self.selected_item < self.checklist.len() {
+            self.checklist.remove(self.selected_item);
+            self.selected_item = self.selected_item.min(self.checklist.len().saturating_sub(1));
+        }
+    }
+
+    pub fn select_prev_checklist_item(&mut self) {
+        self.selected_item = self.selected_item.saturating_sub(1);
+    }
+
+    pub fn select_next_checklist_item(&mut self) {
+        if self.selected_item + 1 < self.checklist.len() {
+            self.selected_item += 1;
+        }
+    }
+
+    /// The editor of the selected field; the priority field has none.
+    pub fn selected_editor_mut(&mut self) -> Option<&mut TextEditor> {
+        match self.selected_field {
+            EditField::Title => Some(&mut self.title),
+            EditField::Description => Some(&mut self.description),
+            EditField::DueDate => Some(&mut self.due_date),
+            EditField::Labels => Some(&mut self.labels),
+            EditField::Checklist => Some(&mut self.checklist_input),
+            EditField::Blockers => Some(&mut self.blocker_query),
+            EditField::Priority => None,
+        }
+    }
+
+    pub fn remove_selected_blocker(&mut self) {
+        if self.selected_blocker < self.blockers.len() {
+            self.blockers.remove(self.selected_blocker);
+            self.selected_blocker = self.selected_blocker.min(self.blockers.len().saturating_sub(1));
+        }
+    }
+}
+
+/// Where an `$EDITOR` session was requested from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalEdit {
+    /// The selected card on the board; changes are saved right away.
+    Board,
+    /// The add/edit popup; changes go back into the popup fields.
+    Popup,
+}
+
+/// Counts of open cards by due status, refreshed on every tick.
+#[derive(Clone, Copy, Default)]
+pub struct DueSummary {
+    pub overdue: usize,
+    pub today: usize,
+    pub this_week: usize,
 }
 
 pub struct App {
@@ -46,6 +219,65 @@ pub struct App {
     pub status_message: String,
     pub moving_task_id: Option<Uuid>,
     pub target_column: usize,
+    pub boards: Vec<BoardSummary>,
+    pub selected_board: usize,
+    pub board_name_input: String,
+    pub renaming_board_id: Option<Uuid>,
+    pub column_name_input: String,
+    pub renaming_column_id: Option<Uuid>,
+    pub wip_limit_input: String,
+    /// Current search text. Non-empty outside search mode means `n`/`N`
+    /// cycle through matches until the search is cleared with Esc.
+    pub search_query: String,
+    /// Limits which tasks are shown; `selected_task` indexes the filtered list.
+    pub filter: TaskFilter,
+    /// Saved view the current filter was loaded from, if unchanged since.
+    pub active_view: Option<Uuid>,
+    /// Whether typing in the filter panel goes to the text criterion.
+    pub filter_text_editing: bool,
+    pub view_name_input: String,
+    /// New name for the label picked in the filter panel.
+    pub label_name_input: String,
+    /// Undo/redo history per board id, kept for the whole session so
+    /// switching boards and back doesn't lose it.
+    pub histories: HashMap<Uuid, History>,
+    /// Archived and trashed tasks listed on the archive screen.
+    pub archive: Vec<HiddenTask>,
+    pub selected_archived: usize,
+    /// Activity log of the task shown in the detail view, oldest first.
+    pub detail_events: Vec<TaskEvent>,
+    /// First visible line of the detail view. May run past the end while
+    /// scrolling; rendering clamps it to the content.
+    pub detail_scroll: u16,
+    pub metrics: Metrics,
+    /// Change revision of the unsaved board and when it was first seen, so
+    /// autosave waits for a quiet moment after the last edit.
+    pub last_change: Option<(u64, Instant)>,
+    pub save_error: Option<String>,
+    /// Failed saves in a row and when autosave may try again.
+    pub save_retry: Option<(u32, Instant)>,
+    pub today: NaiveDate,
+    pub due_summary: DueSummary,
+    pub notifier: Box<dyn Notifier>,
+    /// Cards already reminded about and on which day, so each one fires at
+    /// most once a day.
+    pub notified: HashSet<(Uuid, NaiveDate)>,
+    /// Whether completing a checklist offers to move the card on.
+    pub suggest_next_column: bool,
+    /// Set when the task should be opened in `$EDITOR`. The main loop owns
+    /// the terminal, so it runs the editor and reports back through
+    /// `finish_external_edit`.
+    pub external_edit: Option<ExternalEdit>,
+    /// Screen area of each column as last drawn, for mouse hit-testing.
+    /// Columns on other pages have an empty area.
+    pub column_areas: Vec<Rect>,
+    /// Scroll position of each column's card list by column id. Rendering
+    /// keeps the selected card in view.
+    pub column_states: HashMap<Uuid, ListState>,
+    /// Card under the mouse button while it is held, which a drag moves.
+    pub pressed_task: Option<Uuid>,
+    /// Last card clicked and when, to recognise double-clicks.
+    pub last_click: Option<(Instant, Uuid)>,
 }
 
 impl App {
@@ -59,10 +291,9 @@ impl App {
         let sample_task2 = Task::new("學習 Rust TUI".to_string())
             .with_description("深入了解 ratatui 框架".to_string());
 
-        if let Some(column) = board.columns.get_mut(0) {
-            column.add_task(sample_task1);
-            column.add_task(sample_task2);
-        }
+        let first_column_id = board.columns[0].id;
+        board.create_task(first_column_id, usize::MAX, sample_task1);
+        board.create_task(first_column_id, usize::MAX, sample_task2);
 
         Self {
             board,
@@ -74,10 +305,142 @@ impl App {
             status_message: "Ready".to_string(),
             moving_task_id: None,
             target_column: 0,
+            boards: Vec::new(),
+            selected_board: 0,
+            board_name_input: String::new(),
+            renaming_board_id: None,
+            column_name_input: String::new(),
+            renaming_column_id: None,
+            wip_limit_input: String::new(),
+            search_query: String::new(),
+            filter: TaskFilter::default(),
+            active_view: None,
+            filter_text_editing: false,
+            view_name_input: String::new(),
+            label_name_input: String::new(),
+            histories: HashMap::new(),
+            archive: Vec::new(),
+            selected_archived: 0,
+            detail_events: Vec::new(),
+            detail_scroll: 0,
+            metrics: Metrics::default(),
+            last_change: None,
+            save_error: None,
+            save_retry: None,
+            today: Local::now().date_naive(),
+            due_summary: DueSummary::default(),
+            notifier: Box::new(NoopNotifier),
+            notified: HashSet::new(),
+            suggest_next_column: true,
+            external_edit: None,
+            column_areas: Vec::new(),
+            column_states: HashMap::new(),
+            pressed_task: None,
+            last_click: None,
         }
     }
 
     pub fn tick(&mut self) {
+        self.check_due_dates(Local::now().date_naive());
+        self.autosave();
+    }
+
+    /// Refreshes the due date summary and sends a reminder the first time on
+    /// `today` a card is seen due today or overdue. Cards in the done column
+    /// are skipped.
+    fn check_due_dates(&mut self, today: NaiveDate) {
+        self.today = today;
+        self.notified.retain(|(_, day)| *day == today);
+        let done_column_id = self.board.done_column_id();
+        let mut summary = DueSummary::default();
+
+        for column in &self.board.columns {
+            if Some(column.id) == done_column_id {
+                continue;
+            }
+
+            for task in &column.tasks {
+                let Some(status) = task.due_status(self.today) else {
+                    continue;
+                };
+
+                let reminder = match status {
+                    DueStatus::Overdue => {
+                        summary.overdue += 1;
+                        Some("Task overdue")
+                    }
+                    DueStatus::Today => {
+                        summary.today += 1;
+                        Some("Task due today")
+                    }
+                    DueStatus::ThisWeek => {
+                        summary.this_week += 1;
+                        None
+                    }
+                    DueStatus::Later => None,
+                };
+
+                if let Some(reminder) = reminder
+                    && self.notified.insert((task.id, today))
+                {
+                    self.notifier.notify(reminder, &task.title);
+                }
+            }
+        }
+
+        self.due_summary = summary;
+    }
+
+    fn autosave(&mut self) {
+        if self.autosave_due(Instant::now()) {
+            // Failures are surfaced through `save_error` and retried after a backoff.
+            let _ = self.save();
+        }
+    }
+
+    /// Whether the board has gone `AUTOSAVE_DELAY` without changes and no
+    /// backoff from a failed save is pending.
+    fn autosave_due(&mut self, now: Instant) -> bool {
+        if !self.board.is_dirty() {
+            self.last_change = None;
+            return false;
+        }
+
+        let revision = self.board.changes.revision;
+        let changed_at = match self.last_change {
+            Some((seen, at)) if seen == revision => at,
+            _ => {
+                self.last_change = Some((revision, now));
+                now
+            }
+        };
+        now.duration_since(changed_at) >= AUTOSAVE_DELAY
+            && self.save_retry.is_none_or(|(_, retry_at)| now >= retry_at)
+    }
+
+    /// Writes pending board changes to storage and updates the save indicator.
+    pub fn save(&mut self) -> io::Result<()> {
+        match storage::save_board(&mut self.board) {
+            Ok(()) => {
+                self.last_change = None;
+                self.save_error = None;
+                self.save_retry = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.save_failed(e.to_string(), Instant::now());
+                Err(e)
+            }
+        }
+    }
+
+    fn save_failed(&mut self, error: String, now: Instant) {
+        let failures = self.save_retry.map_or(1, |(failures, _)| failures + 1);
+        let backoff = AUTOSAVE_DELAY
+            .saturating_mul(1 << failures.min(16))
+            .min(MAX_SAVE_BACKOFF);
+        self.save_retry = Some((failures, now + backoff));
+        self.save_error = Some(error);
     }
 
     pub fn quit(&mut self) {
@@ -99,26 +462,34 @@ impl App {
     }
 
     pub fn move_selection_up(&mut self) {
-        if let Some(column) = self.board.columns.get(self.selected_column) {
-            if !column.tasks.is_empty() && self.selected_task > 0 {
-                self.selected_task -= 1;
-            }
+        if self.selected_task > 0 {
+            self.selected_task -= 1;
         }
     }
 
     pub fn move_selection_down(&mut self) {
-        if let Some(column) = self.board.columns.get(self.selected_column) {
-            if !column.tasks.is_empty() && self.selected_task < column.tasks.len().saturating_sub(1) {
-                self.selected_task += 1;
-            }
+        if self.selected_task + 1 < self.visible_tasks(self.selected_column).len() {
+            self.selected_task += 1;
         }
     }
 
-    pub fn get_selected_task_id(&self) -> Option<Uuid> {
+    /// Tasks of a column that pass the current filter, in column order.
+    pub fn visible_tasks(&self, column_index: usize) -> Vec<&Task> {
         self.board
             .columns
-            .get(self.selected_column)?
-            .tasks
+            .get(column_index)
+            .map(|column| {
+                column
+                    .tasks
+                    .iter()
+                    .filter(|task| self.filter.matches(task, self.today))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_selected_task_id(&self) -> Option<Uuid> {
+        self.visible_tasks(self.selected_column)
             .get(self.selected_task)
             .map(|task| task.id)
     }
@@ -127,40 +498,108 @@ impl App {
         self.board.columns.get(self.selected_column).map(|col| col.id)
     }
 
+    /// Column at a screen position and, inside its border, the card row
+    /// counted from the top of the list. Uses the areas and scroll
+    /// positions of the last render.
+    pub fn board_position(&self, x: u16, y: u16) -> Option<(usize, Option<usize>)> {
+        let column_index = self
+            .column_areas
+            .iter()
+            .position(|area| area.contains(Position::new(x, y)))?;
+        let area = self.column_areas[column_index];
+        let offset = self
+            .board
+            .columns
+            .get(column_index)
+            .and_then(|column| self.column_states.get(&column.id))
+            .map_or(0, ListState::offset);
+        let row = (y > area.y && y + 1 < area.bottom()).then(|| (y - area.y - 1) as usize + offset);
+        Some((column_index, row))
+    }
+
+    /// Selects a column and, if `row` holds a visible card, that card.
+    /// Returns the card's id.
+    pub fn select_card(&mut self, column_index: usize, row: Option<usize>) -> Option<Uuid> {
+        if column_index >= self.board.columns.len() {
+            return None;
+        }
+        if column_index != self.selected_column {
+            self.selected_column = column_index;
+            self.selected_task = 0;
+        }
+        let task_id = row.and_then(|row| self.visible_tasks(column_index).get(row).map(|task| task.id));
+        if let Some(row) = row.filter(|_| task_id.is_some()) {
+            self.selected_task = row;
+        }
+        task_id
+    }
+
     pub fn start_adding_task(&mut self) {
         self.input_mode = InputMode::AddingTask;
         self.edit_state = EditState::default();
-        self.status_message = "Enter new task title".to_string();
+        self.status_message = "Adding task".to_string();
     }
 
     pub fn start_editing_task(&mut self) {
-        if let Some(task_id) = self.get_selected_task_id() {
-            if let Some(task) = self.board.get_task(task_id) {
-                self.edit_state = EditState {
-                    title: task.title.clone(),
-                    description: task.description.clone().unwrap_or_default(),
-                    priority: task.priority.clone(),
-                    selected_field: EditField::Title,
-                };
-                self.input_mode = InputMode::Editing;
-                self.status_message = "Editing task".to_string();
-            }
+        if let Some(task_id) = self.get_selected_task_id()
+            && let Some(task) = self.board.get_task(task_id)
+        {
+            self.edit_state = EditState {
+                title: TextEditor::new(task.title.clone()),
+                description: TextEditor::multiline(task.description.clone().unwrap_or_default()),
+                due_date: TextEditor::new(
+                    task.due_date
+                        .map(|date| date.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default(),
+                ),
+                priority: task.priority.clone(),
+                labels: TextEditor::new(
+                    task.labels
+                        .iter()
+                        .filter_map(|label_id| self.board.get_label(*label_id))
+                        .map(|label| label.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                checklist: task.checklist.clone(),
+                selected_item: 0,
+                blockers: task.blocked_by.clone(),
+                selected_field: EditField::Title,
+                ..EditState::default()
+            };
+            self.input_mode = InputMode::Editing;
+            self.status_message = "Editing task".to_string();
         }
     }
 
     pub fn finish_adding_task(&mut self) {
-        if !self.edit_state.title.trim().is_empty() {
-            let task = Task::new(self.edit_state.title.clone())
-                .with_description(if self.edit_state.description.trim().is_empty() {
-                    String::new()
-                } else {
-                    self.edit_state.description.clone()
-                })
-                .with_priority(self.edit_state.priority.clone());
+        let due_date = match self.edit_state.parsed_due_date() {
+            Ok(date) => date.map(to_due_datetime),
+            Err(e) => {
+                self.status_message = e;
+                return;
+            }
+        };
+
+        if !self.edit_state.title.text().trim().is_empty() {
+            let labels = self.resolve_edit_labels();
+            let mut task = Task::new(self.edit_state.title.text().to_string())
+                .with_due_date(due_date)
+                .with_priority(self.edit_state.priority.clone())
+                .with_labels(labels)
+                .with_checklist(self.edit_state.checklist.clone())
+                .with_blockers(self.edit_state.blockers.clone());
+            task.description = self.edited_description();
 
-            if let Some(column) = self.board.columns.get_mut(self.selected_column) {
-                column.add_task(task);
-                self.status_message = "Task added successfully".to_string();
+            if let Some(column) = self.board.columns.get(self.selected_column) {
+                let command = Command::Add {
+                    column_id: column.id,
+                    index: column.tasks.len(),
+                    task,
+                };
+                if self.execute(command) {
+                    self.status_message = "Task added successfully".to_string();
+                }
             }
         }
         self.input_mode = InputMode::Normal;
@@ -168,60 +607,428 @@ impl App {
     }
 
     pub fn finish_editing_task(&mut self) {
-        if let Some(task_id) = self.get_selected_task_id() {
-            if let Some(task) = self.board.get_task_mut(task_id) {
-                task.update_title(self.edit_state.title.clone());
-                task.update_description(if self.edit_state.description.trim().is_empty() {
-                    None
-                } else {
-                    Some(self.edit_state.description.clone())
-                });
-                task.update_priority(self.edit_state.priority.clone());
+        let due_date = match self.edit_state.parsed_due_date() {
+            Ok(date) => date.map(to_due_datetime),
+            Err(e) => {
+                self.status_message = e;
+                return;
+            }
+        };
+
+        let labels = self.resolve_edit_labels();
+
+        if let Some(task_id) = self.get_selected_task_id()
+            && let Some(before) = self.board.get_task(task_id).cloned()
+        {
+            let mut after = before.clone();
+            after.update_title(self.edit_state.title.text().to_string());
+            after.update_description(self.edited_description());
+            after.update_due_date(due_date);
+            after.update_priority(self.edit_state.priority.clone());
+            after.update_labels(labels);
+            after.update_checklist(self.edit_state.checklist.clone());
+            after.update_blockers(self.edit_state.blockers.clone());
+            let completed = !before.is_checklist_complete() && after.is_checklist_complete();
+            if self.execute(Command::Update { before, after }) {
                 self.status_message = "Task updated successfully".to_string();
+                self.input_mode = InputMode::Normal;
+                self.edit_state = EditState::default();
+                if completed {
+                    self.suggest_moving_task(task_id);
+                }
+                return;
             }
         }
         self.input_mode = InputMode::Normal;
         self.edit_state = EditState::default();
     }
 
+    /// Offers to move a task whose checklist was just completed to the next
+    /// column by opening move mode with that column preselected.
+    fn suggest_moving_task(&mut self, task_id: Uuid) {
+        if !self.suggest_next_column || self.selected_column + 1 >= self.board.columns.len() {
+            return;
+        }
+
+        self.moving_task_id = Some(task_id);
+        self.target_column = self.selected_column + 1;
+        self.input_mode = InputMode::MovingTask;
+        self.status_message = format!(
+            "Checklist complete - m/Enter moves it to {}, Esc keeps it here",
+            self.board.columns[self.target_column].title
+        );
+    }
+
+    /// Asks the main loop to open the selected task, or the task in the
+    /// popup, in the external editor.
+    pub fn request_external_edit(&mut self) {
+        match self.input_mode {
+            InputMode::Normal => {
+                self.start_editing_task();
+                if matches!(self.input_mode, InputMode::Editing) {
+                    self.external_edit = Some(ExternalEdit::Board);
+                }
+            }
+            InputMode::AddingTask | InputMode::Editing => {
+                self.external_edit = Some(ExternalEdit::Popup);
+            }
+            _ => {}
+        }
+    }
+
+    /// The popup fields as a Markdown document for the external editor.
+    pub fn external_edit_document(&self) -> Result<String, String> {
+        if let Some(text) = &self.edit_state.rejected_document {
+            return Ok(text.clone());
+        }
+        let document = TaskDocument {
+            title: self.edit_state.title.text().to_string(),
+            priority: self.edit_state.priority.clone(),
+            due_date: self.edit_state.parsed_due_date()?,
+            labels: self.edit_state.label_names().into_iter().map(String::from).collect(),
+            description: self.edit_state.description.text().to_string(),
+        };
+        Ok(document.to_markdown())
+    }
+
+    /// Applies the file saved in the external editor. Parse errors are
+    /// reported in the status bar and leave the task unchanged; the popup
+    /// stays open with the previous fields and keeps the saved text for
+    /// the next external edit.
+    pub fn finish_external_edit(&mut self, edited: io::Result<String>) {
+        let Some(origin) = self.external_edit.take() else {
+            return;
+        };
+
+        let text = match edited {
+            Ok(text) => text,
+            Err(e) => {
+                if origin == ExternalEdit::Board {
+                    self.input_mode = InputMode::Normal;
+                    self.edit_state = EditState::default();
+                }
+                self.status_message = format!("Editor changes not applied: {}", e);
+                return;
+            }
+        };
+        match TaskDocument::parse(&text, self.today) {
+            Ok(document) => {
+                self.edit_state.rejected_document = None;
+                self.edit_state.title = TextEditor::new(document.title);
+                self.edit_state.priority = document.priority;
+                self.edit_state.due_date = TextEditor::new(
+                    document.due_date
+                        .map(|date| date.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default(),
+                );
+                self.edit_state.labels = TextEditor::new(document.labels.join(", "));
+                self.edit_state.description = TextEditor::multiline(document.description);
+                match origin {
+                    ExternalEdit::Board => self.finish_editing_task(),
+                    ExternalEdit::Popup => {
+                        self.status_message = "Fields updated from the editor".to_string();
+                    }
+                }
+            }
+            Err(e) => {
+                self.edit_state.rejected_document = Some(text);
+                self.status_message = format!("Editor changes not applied: {} (Ctrl-O to fix them)", e);
+            }
+        }
+    }
+
+    /// The task open in the edit popup, if it already exists.
+    fn edited_task_id(&self) -> Option<Uuid> {
+        match self.input_mode {
+            InputMode::Editing => self.get_selected_task_id(),
+            _ => None,
+        }
+    }
+
+    /// Tasks whose title matches the blocker search and that can block the
+    /// edited task: not the task itself, not already a blocker and not
+    /// waiting for the task, which would make a cycle.
+    pub fn blocker_candidates(&self) -> Vec<&Task> {
+        let query = self.edit_state.blocker_query.text().trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let edited = self.edited_task_id();
+        let graph = DependencyGraph::new(&self.board);
+        self.board
+            .columns
+            .iter()
+            .flat_map(|column| &column.tasks)
+            .filter(|task| task.title.to_lowercase().contains(&query))
+            .filter(|task| !self.edit_state.blockers.contains(&task.id))
+            .filter(|task| edited.is_none_or(|id| !graph.would_create_cycle(id, task.id)))
+            .collect()
+    }
+
+    /// Adds the highlighted search result as a blocker.
+    pub fn add_selected_blocker(&mut self) {
+        let candidate = self
+            .blocker_candidates()
+            .get(self.edit_state.selected_candidate)
+            .map(|task| task.id);
+        match candidate {
+            Some(blocker_id) => {
+                self.edit_state.blockers.push(blocker_id);
+                self.edit_state.selected_blocker = self.edit_state.blockers.len() - 1;
+                self.edit_state.blocker_query.clear();
+                self.edit_state.selected_candidate = 0;
+            }
+            None => {
+                self.status_message = format!(
+                    "No task matching '{}' can block this one",
+                    self.edit_state.blocker_query.text()
+                );
+            }
+        }
+    }
+
+    /// Moves the highlight through the search results while searching,
+    /// otherwise through the current blockers.
+    pub fn select_prev_blocker(&mut self) {
+        if self.edit_state.blocker_query.is_empty() {
+            self.edit_state.selected_blocker = self.edit_state.selected_blocker.saturating_sub(1);
+        } else {
+            self.edit_state.selected_candidate = self.edit_state.selected_candidate.saturating_sub(1);
+        }
+    }
+
+    pub fn select_next_blocker(&mut self) {
+        if self.edit_state.blocker_query.is_empty() {
+            if self.edit_state.selected_blocker + 1 < self.edit_state.blockers.len() {
+                self.edit_state.selected_blocker += 1;
+            }
+        } else if self.edit_state.selected_candidate + 1 < self.blocker_candidates().len() {
+            self.edit_state.selected_candidate += 1;
+        }
+    }
+
+    /// The description typed in the popup, or none if it is blank.
+    fn edited_description(&self) -> Option<String> {
+        let text = self.edit_state.description.text();
+        (!text.trim().is_empty()).then(|| text.to_string())
+    }
+
+    /// Maps the label names typed in the popup to label ids, registering
+    /// labels that don't exist on the board yet.
+    fn resolve_edit_labels(&mut self) -> Vec<Uuid> {
+        let names: Vec<String> = self.edit_state.label_names().into_iter().map(String::from).collect();
+        names.iter().map(|name| self.board.ensure_label(name)).collect()
+    }
+
     pub fn cancel_input(&mut self) {
         self.input_mode = InputMode::Normal;
         self.edit_state = EditState::default();
         self.moving_task_id = None;
+        self.pressed_task = None;
         self.status_message = "Cancelled".to_string();
     }
 
+    /// Moves the selected task to the trash.
     pub fn delete_selected_task(&mut self) {
-        if let Some(task_id) = self.get_selected_task_id() {
-            if self.board.delete_task(task_id) {
-                if let Some(column) = self.board.columns.get(self.selected_column) {
-                    if self.selected_task >= column.tasks.len() && !column.tasks.is_empty() {
-                        self.selected_task = column.tasks.len() - 1;
-                    }
+        if self.hide_selected_task(HiddenState::Deleted) {
+            self.status_message = "Task moved to trash (u to undo, t to view)".to_string();
+        }
+    }
+
+    pub fn archive_selected_task(&mut self) {
+        if self.hide_selected_task(HiddenState::Archived) {
+            self.status_message = "Task archived (u to undo, t to view)".to_string();
+        }
+    }
+
+    fn hide_selected_task(&mut self, state: HiddenState) -> bool {
+        let Some(command) = self.get_selected_task_id().and_then(|task_id| self.hide_command(task_id, state)) else {
+            return false;
+        };
+        let hidden = self.execute(command);
+        self.validate_selection();
+        hidden
+    }
+
+    fn hide_command(&self, task_id: Uuid, state: HiddenState) -> Option<Command> {
+        let (column_id, index) = self.board.locate_task(task_id)?;
+        let task = self.board.get_task(task_id)?.clone();
+        Some(Command::Hide { column_id, index, task, state })
+    }
+
+    /// Archives every task in the selected column, typically Done.
+    pub fn archive_selected_column(&mut self) {
+        let Some(column) = self.board.columns.get(self.selected_column) else {
+            return;
+        };
+        let title = column.title.clone();
+        // Positions as seen when the batch runs in order: every earlier task
+        // is already gone, so each one is first in the column.
+        let commands: Vec<Command> = column
+            .tasks
+            .iter()
+            .filter_map(|task| self.hide_command(task.id, HiddenState::Archived))
+            .map(|mut command| {
+                if let Command::Hide { index, .. } = &mut command {
+                    *index = 0;
                 }
-                self.status_message = "Task deleted successfully".to_string();
+                command
+            })
+            .collect();
+        if commands.is_empty() {
+            self.status_message = format!("Nothing to archive in {}", title);
+            return;
+        }
+
+        let count = commands.len();
+        if self.execute(Command::Batch(commands)) {
+            self.validate_selection();
+            self.status_message = format!("Archived {} tasks from {} (u to undo)", count, title);
+        }
+    }
+
+    pub fn move_task_to_prev_column(&mut self) {
+        if let Some(task_id) = self.get_selected_task_id()
+            && self.selected_column > 0
+            && self.move_task_to_column(task_id, self.selected_column - 1)
+        {
+            self.status_message = "Task moved to previous column".to_string();
+            self.append_wip_warning(self.selected_column - 1);
+            self.validate_selection();
+        }
+    }
+
+    /// Appends a task to the column at `column_index`, unless the column's
+    /// WIP limit blocks it.
+    fn move_task_to_column(&mut self, task_id: Uuid, column_index: usize) -> bool {
+        let Some(column) = self.board.columns.get(column_index) else {
+            return false;
+        };
+        if column.task_index(task_id).is_some() {
+            return false;
+        }
+        let index = column.tasks.len();
+        self.move_task_to(task_id, column_index, index)
+    }
+
+    /// Moves a task to `index` in the column at `column_index`, unless the
+    /// column's WIP limit blocks it.
+    fn move_task_to(&mut self, task_id: Uuid, column_index: usize, index: usize) -> bool {
+        let Some(from) = self.board.locate_task(task_id) else {
+            return false;
+        };
+        let Some(column) = self.board.columns.get(column_index) else {
+            return false;
+        };
+        if column.id != from.0 && self.board.is_move_blocked(column.id) {
+            self.status_message = format!(
+                "{} is at its WIP limit ({}/{})",
+                column.title,
+                column.tasks.len(),
+                column.wip_limit.unwrap_or_default()
+            );
+            return false;
+        }
+
+        let to = (column.id, index);
+        to != from && self.execute(Command::Move { task_id, from, to })
+    }
+
+    pub fn move_selected_task_up(&mut self) {
+        if self.selected_task > 0 && self.swap_with_visible_task(self.selected_task - 1) {
+            self.selected_task -= 1;
+            self.status_message = "Task moved up".to_string();
+        }
+    }
+
+    pub fn move_selected_task_down(&mut self) {
+        if self.swap_with_visible_task(self.selected_task + 1) {
+            self.selected_task += 1;
+            self.status_message = "Task moved down".to_string();
+        }
+    }
+
+    /// Moves the selected task to the slot of the visible task at `target`,
+    /// so reordering under a filter steps over hidden tasks.
+    fn swap_with_visible_task(&mut self, target: usize) -> bool {
+        let visible = self.visible_tasks(self.selected_column);
+        let (Some(task), Some(neighbour)) = (visible.get(self.selected_task), visible.get(target)) else {
+            return false;
+        };
+        let (task_id, neighbour_id) = (task.id, neighbour.id);
+
+        let (Some(from), Some(to)) = (self.board.locate_task(task_id), self.board.locate_task(neighbour_id)) else {
+            return false;
+        };
+        self.execute(Command::Move { task_id, from, to })
+    }
+
+    fn history(&mut self) -> &mut History {
+        self.histories.entry(self.board.id).or_default()
+    }
+
+    /// Applies a task mutation and records it so it can be undone.
+    fn execute(&mut self, command: Command) -> bool {
+        if command.apply(&mut self.board) {
+            self.history().record(command);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn undo(&mut self) {
+        let Some(command) = self.history().pop_undo() else {
+            self.status_message = "Nothing to undo".to_string();
+            return;
+        };
+
+        let description = command.describe(&self.board);
+        if command.inverse().replay(&mut self.board) {
+            if let Some(task_id) = command.task_id() {
+                self.select_task(task_id);
             }
+            self.history().push_redo(command);
+            self.status_message = format!("Undid {}", description);
+        } else {
+            self.status_message = format!("Cannot undo {}: the board has changed", description);
         }
     }
 
+    pub fn redo(&mut self) {
+        let Some(command) = self.history().pop_redo() else {
+            self.status_message = "Nothing to redo".to_string();
+            return;
+        };
 
-    pub fn move_task_to_prev_column(&mut self) {
-        if let Some(task_id) = self.get_selected_task_id() {
-            let from_column_id = self.get_selected_column_id().unwrap();
-            
-            if self.selected_column > 0 {
-                let to_column_id = self.board.columns[self.selected_column - 1].id;
-                if self.board.move_task(task_id, from_column_id, to_column_id) {
-                    self.status_message = "Task moved to previous column".to_string();
-                    
-                    if let Some(column) = self.board.columns.get(self.selected_column) {
-                        if self.selected_task >= column.tasks.len() && !column.tasks.is_empty() {
-                            self.selected_task = column.tasks.len() - 1;
-                        }
-                    }
-                }
+        let description = command.describe(&self.board);
+        if command.replay(&mut self.board) {
+            if let Some(task_id) = command.task_id() {
+                self.select_task(task_id);
+            }
+            self.history().push_undo(command);
+            self.status_message = format!("Redid {}", description);
+        } else {
+            self.status_message = format!("Cannot redo {}: the board has changed", description);
+        }
+    }
+
+    /// Moves the selection to a task, if it exists and passes the filter.
+    fn select_task(&mut self, task_id: Uuid) {
+        if let Some((column_id, _)) = self.board.locate_task(task_id)
+            && let Some(column_index) = self.board.column_index(column_id)
+        {
+            self.selected_column = column_index;
+            if let Some(index) = self
+                .visible_tasks(column_index)
+                .iter()
+                .position(|task| task.id == task_id)
+            {
+                self.selected_task = index;
             }
         }
+        self.validate_selection();
     }
 
     pub fn start_moving_task(&mut self) {
@@ -248,54 +1055,1341 @@ impl App {
     pub fn move_edit_field_next(&mut self) {
         self.edit_state.selected_field = match self.edit_state.selected_field {
             EditField::Title => EditField::Description,
-            EditField::Description => EditField::Priority,
-            EditField::Priority => EditField::Title, // Cycle back to first field
+            EditField::Description => EditField::DueDate,
+            EditField::DueDate => EditField::Priority,
+            EditField::Priority => EditField::Labels,
+            EditField::Labels => EditField::Checklist,
+            EditField::Checklist => EditField::Blockers,
+            EditField::Blockers => EditField::Title, // Cycle back to first field
         };
     }
 
     pub fn move_edit_field_prev(&mut self) {
         self.edit_state.selected_field = match self.edit_state.selected_field {
-            EditField::Title => EditField::Priority,
+            EditField::Title => EditField::Blockers,
             EditField::Description => EditField::Title,
-            EditField::Priority => EditField::Description,
+            EditField::DueDate => EditField::Description,
+            EditField::Priority => EditField::DueDate,
+            EditField::Labels => EditField::Priority,
+            EditField::Checklist => EditField::Labels,
+            EditField::Blockers => EditField::Checklist,
         };
     }
 
     
 
     pub fn confirm_move_task(&mut self) {
-        if let Some(task_id) = self.moving_task_id {
-            let from_column_id = self.get_selected_column_id().unwrap();
-            let to_column_id = self.board.columns[self.target_column].id;
-            
-            if self.board.move_task(task_id, from_column_id, to_column_id) {
-                let target_column_name = &self.board.columns[self.target_column].title;
-                self.status_message = format!("Task moved to {}", target_column_name);
-                
-                // Adjust selection if current column lost tasks
-                if let Some(column) = self.board.columns.get(self.selected_column) {
-                    if self.selected_task >= column.tasks.len() && !column.tasks.is_empty() {
-                        self.selected_task = column.tasks.len() - 1;
-                    }
-                }
-            }
+        if let Some(task_id) = self.moving_task_id
+            && self.move_task_to_column(task_id, self.target_column)
+        {
+            let target_column_name = &self.board.columns[self.target_column].title;
+            self.status_message = format!("Task moved to {}", target_column_name);
+            self.append_wip_warning(self.target_column);
+            self.append_blocker_warning(task_id, self.target_column);
+
+            // Adjust selection if current column lost tasks
+            self.validate_selection();
         }
         
         self.input_mode = InputMode::Normal;
         self.moving_task_id = None;
+        self.pressed_task = None;
     }
 
-    pub fn validate_selection(&mut self) {
-        // Ensure selected_column is valid
-        if self.selected_column >= self.board.columns.len() {
-            self.selected_column = if self.board.columns.is_empty() { 0 } else { self.board.columns.len() - 1 };
+    /// Starts moving the pressed card with the mouse; releasing the button
+    /// over a column drops it there.
+    pub fn start_dragging_task(&mut self) {
+        if let Some(task_id) = self.pressed_task {
+            self.moving_task_id = Some(task_id);
+            self.target_column = self.selected_column;
+            self.input_mode = InputMode::MovingTask;
+            self.status_message = "Drop the card on a column (Esc to cancel)".to_string();
         }
-        
-        // Ensure selected_task is valid for the current column
-        if let Some(column) = self.board.columns.get(self.selected_column) {
-            if self.selected_task >= column.tasks.len() {
-                self.selected_task = if column.tasks.is_empty() { 0 } else { column.tasks.len() - 1 };
+    }
+
+    /// Drops the card being moved into the column at `column_index`, in
+    /// place of the card at visible `row`, or at the end below the last one.
+    pub fn drop_moving_task(&mut self, column_index: usize, row: Option<usize>) {
+        self.input_mode = InputMode::Normal;
+        let Some(task_id) = self.moving_task_id.take() else {
+            return;
+        };
+        let Some(column) = self.board.columns.get(column_index) else {
+            return;
+        };
+        let same_column = column.task_index(task_id).is_some();
+        let index = row
+            .and_then(|row| self.visible_tasks(column_index).get(row).map(|task| task.id))
+            .and_then(|id| column.task_index(id))
+            .unwrap_or(column.tasks.len());
+        let index = if same_column { index.min(column.tasks.len() - 1) } else { index };
+
+        // Replaced by the WIP limit message if that is what stops the move
+        self.status_message = "Task not moved".to_string();
+        if self.move_task_to(task_id, column_index, index) {
+            let title = &self.board.columns[column_index].title;
+            if same_column {
+                self.status_message = format!("Task reordered in {}", title);
+            } else {
+                self.status_message = format!("Task moved to {}", title);
+                self.append_wip_warning(column_index);
+                self.append_blocker_warning(task_id, column_index);
             }
+            self.select_task(task_id);
+        }
+    }
+
+    /// Adds a warning to the status message when the column is over its limit.
+    fn append_wip_warning(&mut self, column_index: usize) {
+        if let Some(column) = self.board.columns.get(column_index)
+            && column.is_over_limit()
+        {
+            self.status_message.push_str(&format!(
+                " - {} is over its WIP limit ({}/{})",
+                column.title,
+                column.tasks.len(),
+                column.wip_limit.unwrap_or_default()
+            ));
+        }
+    }
+
+    /// Adds a warning to the status message when a task that is still
+    /// blocked lands in the done column.
+    fn append_blocker_warning(&mut self, task_id: Uuid, column_index: usize) {
+        if column_index + 1 != self.board.columns.len() {
+            return;
+        }
+        let Some(task) = self.board.get_task(task_id) else {
+            return;
+        };
+        let blockers: Vec<&str> = self
+            .board
+            .open_blockers(task)
+            .iter()
+            .map(|blocker| blocker.title.as_str())
+            .collect();
+        if !blockers.is_empty() {
+            self.status_message.push_str(&format!(" - still blocked by {}", blockers.join(", ")));
+        }
+    }
+
+    pub fn start_managing_columns(&mut self) {
+        self.input_mode = InputMode::ManagingColumns;
+        self.status_message = "Managing columns".to_string();
+    }
+
+    pub fn stop_managing_columns(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.status_message = "Ready".to_string();
+    }
+
+    pub fn start_adding_column(&mut self) {
+        self.column_name_input.clear();
+        self.renaming_column_id = None;
+        self.input_mode = InputMode::NamingColumn;
+        self.status_message = "Enter new column name".to_string();
+    }
+
+    pub fn start_renaming_column(&mut self) {
+        if let Some(column) = self.board.columns.get(self.selected_column) {
+            self.column_name_input = column.title.clone();
+            self.renaming_column_id = Some(column.id);
+            self.input_mode = InputMode::NamingColumn;
+            self.status_message = "Enter column name".to_string();
         }
     }
-}
\ No newline at end of file
+
+    pub fn cancel_naming_column(&mut self) {
+        self.column_name_input.clear();
+        self.renaming_column_id = None;
+        self.input_mode = InputMode::ManagingColumns;
+        self.status_message = "Cancelled".to_string();
+    }
+
+    pub fn finish_naming_column(&mut self) {
+        let name = self.column_name_input.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        match self.renaming_column_id {
+            Some(column_id) => {
+                self.board.rename_column(column_id, name.clone());
+                self.status_message = format!("Column renamed to {}", name);
+            }
+            None => {
+                // New columns go right after the selected one.
+                let index = if self.board.columns.is_empty() { 0 } else { self.selected_column + 1 };
+                self.board.insert_column(index, name.clone());
+                self.selected_column = index;
+                self.selected_task = 0;
+                self.status_message = format!("Column {} added", name);
+            }
+        }
+
+        self.column_name_input.clear();
+        self.renaming_column_id = None;
+        self.input_mode = InputMode::ManagingColumns;
+        self.validate_selection();
+    }
+
+    pub fn start_setting_wip_limit(&mut self) {
+        if let Some(column) = self.board.columns.get(self.selected_column) {
+            self.wip_limit_input = column.wip_limit.map(|limit| limit.to_string()).unwrap_or_default();
+            self.input_mode = InputMode::SettingWipLimit;
+            self.status_message = "Enter WIP limit, empty for none".to_string();
+        }
+    }
+
+    pub fn cancel_setting_wip_limit(&mut self) {
+        self.wip_limit_input.clear();
+        self.input_mode = InputMode::ManagingColumns;
+        self.status_message = "Cancelled".to_string();
+    }
+
+    pub fn finish_setting_wip_limit(&mut self) {
+        let limit = match self.wip_limit_input.trim() {
+            "" | "0" => None,
+            input => match input.parse::<usize>() {
+                Ok(limit) => Some(limit),
+                Err(_) => {
+                    self.status_message = format!("Invalid WIP limit: {}", input);
+                    return;
+                }
+            },
+        };
+
+        if let Some(column) = self.board.columns.get(self.selected_column) {
+            let (column_id, title, policy) = (column.id, column.title.clone(), column.wip_policy);
+            self.board.set_wip_limit(column_id, limit, policy);
+            self.status_message = match limit {
+                Some(limit) => format!("{} limited to {} tasks", title, limit),
+                None => format!("{} has no WIP limit", title),
+            };
+        }
+
+        self.wip_limit_input.clear();
+        self.input_mode = InputMode::ManagingColumns;
+    }
+
+    /// Switches the selected column between warning and blocking when its
+    /// WIP limit is reached.
+    pub fn toggle_wip_policy(&mut self) {
+        if let Some(column) = self.board.columns.get(self.selected_column) {
+            let policy = match column.wip_policy {
+                WipPolicy::Warn => WipPolicy::Block,
+                WipPolicy::Block => WipPolicy::Warn,
+            };
+            let (column_id, title, limit) = (column.id, column.title.clone(), column.wip_limit);
+            self.board.set_wip_limit(column_id, limit, policy);
+            self.status_message = match policy {
+                WipPolicy::Warn => format!("{} warns when over its WIP limit", title),
+                WipPolicy::Block => format!("{} blocks moves past its WIP limit", title),
+            };
+        }
+    }
+
+    /// Column that receives the tasks of the selected column when it is
+    /// deleted: the one to its left, or to its right for the first column.
+    pub fn column_delete_target(&self) -> Option<usize> {
+        if self.selected_column > 0 {
+            Some(self.selected_column - 1)
+        } else if self.board.columns.len() > 1 {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    pub fn request_delete_column(&mut self) {
+        let Some(column) = self.board.columns.get(self.selected_column) else {
+            return;
+        };
+
+        if self.board.columns.len() <= 1 {
+            self.status_message = "Cannot delete the only column".to_string();
+        } else if column.tasks.is_empty() {
+            self.delete_selected_column(false);
+        } else {
+            self.input_mode = InputMode::DeletingColumn;
+            self.status_message = format!("Column {} has {} tasks", column.title, column.tasks.len());
+        }
+    }
+
+    /// Deletes the selected column, moving its tasks to the neighbouring
+    /// column when `move_tasks` is set and trashing them otherwise. Moving
+    /// is refused when the tasks don't fit a blocking WIP limit.
+    pub fn delete_selected_column(&mut self, move_tasks: bool) {
+        let Some(column_id) = self.get_selected_column_id() else {
+            return;
+        };
+        let target_id = if move_tasks {
+            self.column_delete_target().map(|idx| self.board.columns[idx].id)
+        } else {
+            None
+        };
+        let column = &self.board.columns[self.selected_column];
+        let (title, task_count) = (column.title.clone(), column.tasks.len());
+
+        if let Some(target) = target_id.and_then(|id| self.board.columns.iter().find(|column| column.id == id))
+            && self.board.are_moves_blocked(target.id, task_count)
+        {
+            self.status_message = format!(
+                "{} is limited to {} tasks - x moves them to the trash instead",
+                target.title,
+                target.wip_limit.unwrap_or_default()
+            );
+            return;
+        }
+
+        if self.board.delete_column(column_id, target_id) {
+            self.selected_column = self.selected_column.saturating_sub(1);
+            self.selected_task = 0;
+            self.validate_selection();
+            let over_limit = target_id
+                .and_then(|id| self.board.columns.iter().find(|column| column.id == id))
+                .filter(|target| target.is_over_limit());
+            self.status_message = match over_limit {
+                _ if target_id.is_none() && task_count > 0 => {
+                    format!("Column {} deleted, {} tasks moved to the trash (t to view)", title, task_count)
+                }
+                Some(target) => format!("Column {} deleted, {} is now over its WIP limit", title, target.title),
+                None => format!("Column {} deleted", title),
+            };
+        }
+        self.input_mode = InputMode::ManagingColumns;
+    }
+
+    pub fn cancel_delete_column(&mut self) {
+        self.input_mode = InputMode::ManagingColumns;
+        self.status_message = "Cancelled".to_string();
+    }
+
+    pub fn move_column_left(&mut self) {
+        if self.selected_column > 0
+            && let Some(column_id) = self.get_selected_column_id()
+            && self.board.move_column(column_id, self.selected_column - 1)
+        {
+            self.selected_column -= 1;
+        }
+    }
+
+    pub fn move_column_right(&mut self) {
+        if let Some(column_id) = self.get_selected_column_id()
+            && self.board.move_column(column_id, self.selected_column + 1)
+        {
+            self.selected_column += 1;
+        }
+    }
+
+    pub fn open_board_picker(&mut self) {
+        // Persist the current board first so it shows up with accurate counts.
+        if let Err(e) = self.save() {
+            self.status_message = format!("Failed to save board: {}", e);
+            return;
+        }
+
+        match storage::list_boards() {
+            Ok(boards) => {
+                self.selected_board = boards
+                    .iter()
+                    .position(|summary| summary.id == self.board.id)
+                    .unwrap_or(0);
+                self.boards = boards;
+                self.input_mode = InputMode::BoardPicker;
+                self.status_message = "Select a board".to_string();
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to list boards: {}", e);
+            }
+        }
+    }
+
+    pub fn close_board_picker(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.status_message = "Ready".to_string();
+    }
+
+    pub fn move_board_selection_up(&mut self) {
+        if self.selected_board > 0 {
+            self.selected_board -= 1;
+        }
+    }
+
+    pub fn move_board_selection_down(&mut self) {
+        if self.selected_board < self.boards.len().saturating_sub(1) {
+            self.selected_board += 1;
+        }
+    }
+
+    pub fn switch_to_selected_board(&mut self) {
+        let Some(summary) = self.boards.get(self.selected_board).cloned() else {
+            return;
+        };
+
+        if summary.id != self.board.id {
+            if let Err(e) = self.save() {
+                self.status_message = format!("Failed to save board: {}", e);
+                return;
+            }
+
+            match storage::load_board(summary.id) {
+                Ok(board) => self.replace_board(board),
+                Err(e) => {
+                    self.status_message = format!("Failed to load board: {}", e);
+                    return;
+                }
+            }
+        }
+
+        self.input_mode = InputMode::Normal;
+        self.status_message = format!("Switched to {}", self.board.title);
+    }
+
+    /// Shows another board from the top. The filter is dropped since it may
+    /// refer to labels of the previous board.
+    fn replace_board(&mut self, board: Board) {
+        self.board = board;
+        self.filter = TaskFilter::default();
+        self.active_view = None;
+        self.selected_column = 0;
+        self.selected_task = 0;
+        self.validate_selection();
+    }
+
+    pub fn start_creating_board(&mut self) {
+        self.board_name_input.clear();
+        self.renaming_board_id = None;
+        self.input_mode = InputMode::NamingBoard;
+        self.status_message = "Enter new board name".to_string();
+    }
+
+    pub fn start_renaming_board(&mut self) {
+        if let Some(summary) = self.boards.get(self.selected_board) {
+            self.board_name_input = summary.title.clone();
+            self.renaming_board_id = Some(summary.id);
+            self.input_mode = InputMode::NamingBoard;
+            self.status_message = "Enter board name".to_string();
+        }
+    }
+
+    pub fn cancel_naming_board(&mut self) {
+        self.board_name_input.clear();
+        self.renaming_board_id = None;
+        self.input_mode = InputMode::BoardPicker;
+        self.status_message = "Cancelled".to_string();
+    }
+
+    pub fn finish_naming_board(&mut self) {
+        let name = self.board_name_input.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        let result = match self.renaming_board_id {
+            Some(board_id) => {
+                let result = if board_id == self.board.id {
+                    self.board.rename(name.clone());
+                    self.save()
+                } else {
+                    storage::rename_board(board_id, &name)
+                };
+                result.map(|_| format!("Board renamed to {}", name))
+            }
+            None => {
+                let mut board = Board::new(name.clone());
+                storage::save_board(&mut board).map(|_| format!("Board {} created", name))
+            }
+        };
+
+        match result.and_then(|message| storage::list_boards().map(|boards| (message, boards))) {
+            Ok((message, boards)) => {
+                if self.renaming_board_id.is_none() {
+                    self.selected_board = boards.len().saturating_sub(1);
+                }
+                self.boards = boards;
+                self.status_message = message;
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to save board: {}", e);
+            }
+        }
+
+        self.board_name_input.clear();
+        self.renaming_board_id = None;
+        self.input_mode = InputMode::BoardPicker;
+    }
+
+    pub fn delete_selected_board(&mut self) {
+        let Some(summary) = self.boards.get(self.selected_board).cloned() else {
+            return;
+        };
+
+        if self.boards.len() <= 1 {
+            self.status_message = "Cannot delete the only board".to_string();
+            return;
+        }
+
+        if let Err(e) = storage::delete_board(summary.id) {
+            self.status_message = format!("Failed to delete board: {}", e);
+            return;
+        }
+        self.histories.remove(&summary.id);
+
+        self.boards.remove(self.selected_board);
+        if self.selected_board >= self.boards.len() {
+            self.selected_board = self.boards.len() - 1;
+        }
+
+        // The open board is gone, so fall back to whichever board is now selected.
+        if summary.id == self.board.id {
+            match storage::load_board(self.boards[self.selected_board].id) {
+                Ok(board) => self.replace_board(board),
+                Err(e) => {
+                    self.status_message = format!("Failed to load board: {}", e);
+                    return;
+                }
+            }
+        }
+
+        self.status_message = format!("Board {} deleted", summary.title);
+    }
+
+    pub fn start_search(&mut self) {
+        self.search_query.clear();
+        self.input_mode = InputMode::Searching;
+    }
+
+    pub fn push_search_char(&mut self, c: char) {
+        self.search_query.push(c);
+        self.jump_to_first_search_hit();
+    }
+
+    pub fn pop_search_char(&mut self) {
+        self.search_query.pop();
+        self.jump_to_first_search_hit();
+    }
+
+    /// Leaves search mode but keeps the query so `n`/`N` keep working.
+    pub fn confirm_search(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let hits = self.search_hits().len();
+        self.status_message = if self.search_query.is_empty() {
+            "Ready".to_string()
+        } else {
+            format!("{} matches for \"{}\"", hits, self.search_query)
+        };
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.input_mode = InputMode::Normal;
+        self.status_message = "Search cleared".to_string();
+    }
+
+    pub fn is_search_active(&self) -> bool {
+        !self.search_query.is_empty()
+    }
+
+    /// Whether a task's title or description contains the search query,
+    /// ignoring case, by the same rule that highlights matches.
+    pub fn task_matches_search(&self, task: &Task) -> bool {
+        search::matches(&task.title, &self.search_query)
+            || task
+                .description
+                .as_ref()
+                .is_some_and(|desc| search::matches(desc, &self.search_query))
+    }
+
+    /// Positions (column, visible task) of all matching tasks in board
+    /// order. Tasks hidden by the filter are never hits.
+    pub fn search_hits(&self) -> Vec<(usize, usize)> {
+        (0..self.board.columns.len())
+            .flat_map(|col_idx| {
+                self.visible_tasks(col_idx)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, task)| self.task_matches_search(task))
+                    .map(move |(task_idx, _)| (col_idx, task_idx))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn jump_to_first_search_hit(&mut self) {
+        if let Some(&(column, task)) = self.search_hits().first() {
+            self.selected_column = column;
+            self.selected_task = task;
+        }
+    }
+
+    pub fn search_next(&mut self) {
+        self.jump_to_search_hit(true);
+    }
+
+    pub fn search_prev(&mut self) {
+        self.jump_to_search_hit(false);
+    }
+
+    fn jump_to_search_hit(&mut self, forward: bool) {
+        let hits = self.search_hits();
+        if hits.is_empty() {
+            self.status_message = format!("No matches for \"{}\"", self.search_query);
+            return;
+        }
+
+        let current = (self.selected_column, self.selected_task);
+        let index = if forward {
+            hits.iter().position(|&hit| hit > current).unwrap_or(0)
+        } else {
+            hits.iter().rposition(|&hit| hit < current).unwrap_or(hits.len() - 1)
+        };
+
+        (self.selected_column, self.selected_task) = hits[index];
+        self.status_message = format!("Match {}/{} for \"{}\"", index + 1, hits.len(), self.search_query);
+    }
+
+    pub fn validate_selection(&mut self) {
+        // Ensure selected_column is valid
+        if self.selected_column >= self.board.columns.len() {
+            self.selected_column = if self.board.columns.is_empty() { 0 } else { self.board.columns.len() - 1 };
+        }
+        
+        // Ensure selected_task is valid for the current column
+        let visible = self.visible_tasks(self.selected_column).len();
+        if self.selected_task >= visible {
+            self.selected_task = visible.saturating_sub(1);
+        }
+    }
+
+    /// Opens the detail view of the selected task with its activity log.
+    /// Pending changes are saved first so the log is complete.
+    pub fn open_task_detail(&mut self) {
+        let Some(task_id) = self.get_selected_task_id() else {
+            return;
+        };
+        if let Err(e) = self.save() {
+            self.status_message = format!("Failed to save board: {}", e);
+            return;
+        }
+
+        match storage::load_task_events(task_id) {
+            Ok(events) => {
+                self.detail_events = events;
+                self.detail_scroll = 0;
+                self.input_mode = InputMode::TaskDetail;
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to load task history: {}", e);
+            }
+        }
+    }
+
+    pub fn close_task_detail(&mut self) {
+        self.detail_events.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn scroll_detail_up(&mut self, lines: u16) {
+        self.detail_scroll = self.detail_scroll.saturating_sub(lines);
+    }
+
+    pub fn scroll_detail_down(&mut self, lines: u16) {
+        self.detail_scroll = self.detail_scroll.saturating_add(lines);
+    }
+
+    /// Computes flow metrics from the stored activity log of this board.
+    pub fn open_metrics(&mut self) {
+        if let Err(e) = self.save() {
+            self.status_message = format!("Failed to save board: {}", e);
+            return;
+        }
+
+        match storage::load_board_events(self.board.id) {
+            Ok(events) => {
+                self.metrics = metrics::compute(&self.board, &events, Utc::now());
+                self.input_mode = InputMode::Metrics;
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to load board history: {}", e);
+            }
+        }
+    }
+
+    pub fn close_metrics(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Shows archived and trashed tasks. The board is saved first because
+    /// the list is read from storage.
+    pub fn open_archive(&mut self) {
+        if let Err(e) = self.save() {
+            self.status_message = format!("Failed to save board: {}", e);
+            return;
+        }
+
+        match storage::load_hidden_tasks(self.board.id) {
+            Ok(archive) => {
+                self.board.remember_hidden(&archive);
+                self.archive = archive;
+                self.selected_archived = 0;
+                self.input_mode = InputMode::Archive;
+                self.status_message = format!("{} archived or deleted tasks", self.archive.len());
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to load archive: {}", e);
+            }
+        }
+    }
+
+    pub fn close_archive(&mut self) {
+        self.archive.clear();
+        self.input_mode = InputMode::Normal;
+        self.status_message = "Ready".to_string();
+    }
+
+    pub fn move_archive_selection_up(&mut self) {
+        if self.selected_archived > 0 {
+            self.selected_archived -= 1;
+        }
+    }
+
+    pub fn move_archive_selection_down(&mut self) {
+        if self.selected_archived + 1 < self.archive.len() {
+            self.selected_archived += 1;
+        }
+    }
+
+    /// Puts the selected archived task back at the end of its original
+    /// column, or the first column if that one is gone.
+    pub fn restore_archived_task(&mut self) {
+        if self.selected_archived >= self.archive.len() {
+            return;
+        }
+        let hidden = &self.archive[self.selected_archived];
+        let Some(state) = hidden.task.hidden_state() else {
+            return;
+        };
+        let column = self
+            .board
+            .columns
+            .iter()
+            .find(|column| column.id == hidden.column_id)
+            .or_else(|| self.board.columns.first());
+        let Some(column) = column else {
+            return;
+        };
+        let column_title = column.title.clone();
+
+        let command = Command::Restore {
+            column_id: column.id,
+            index: column.tasks.len(),
+            task: hidden.task.clone(),
+            state,
+        };
+        if self.execute(command) {
+            let hidden = self.archive.remove(self.selected_archived);
+            self.clamp_archive_selection();
+            self.status_message = format!("Restored \"{}\" to {}", hidden.task.title, column_title);
+        }
+    }
+
+    /// Deletes the selected archived task for good. This cannot be undone.
+    pub fn purge_archived_task(&mut self) {
+        if self.selected_archived >= self.archive.len() {
+            return;
+        }
+        let hidden = self.archive.remove(self.selected_archived);
+        self.board.purge_task(hidden.task.id);
+        self.history().forget(hidden.task.id);
+        self.clamp_archive_selection();
+        self.status_message = format!("Purged \"{}\"", hidden.task.title);
+    }
+
+    fn clamp_archive_selection(&mut self) {
+        self.selected_archived = self.selected_archived.min(self.archive.len().saturating_sub(1));
+    }
+
+    pub fn open_filter_panel(&mut self) {
+        self.filter_text_editing = false;
+        self.input_mode = InputMode::Filtering;
+        self.status_message = "Filter tasks".to_string();
+    }
+
+    pub fn close_filter_panel(&mut self) {
+        self.filter_text_editing = false;
+        self.input_mode = InputMode::Normal;
+        self.status_message = if self.filter.is_empty() {
+            "Filter cleared".to_string()
+        } else {
+            format!("Filter: {}", self.filter_description())
+        };
+    }
+
+    pub fn is_filter_active(&self) -> bool {
+        !self.filter.is_empty()
+    }
+
+    /// Header text for the current filter, prefixed by the view name when
+    /// it came from a saved view.
+    pub fn filter_description(&self) -> String {
+        let description = self.filter.describe(&self.board);
+        match self.active_view.and_then(|id| self.board.views.iter().find(|v| v.id == id)) {
+            Some(view) => format!("{}: {}", view.name, description),
+            None => description,
+        }
+    }
+
+    /// Applies an edit to the filter. The result no longer matches a saved
+    /// view, and the selection is clamped to the new visible list.
+    fn update_filter(&mut self, edit: impl FnOnce(&mut TaskFilter)) {
+        edit(&mut self.filter);
+        self.active_view = None;
+        self.validate_selection();
+    }
+
+    pub fn toggle_priority_filter(&mut self, priority: Priority) {
+        self.update_filter(|filter| filter.toggle_priority(priority));
+    }
+
+    pub fn cycle_due_filter(&mut self) {
+        self.update_filter(|filter| filter.due = DueFilter::cycle(filter.due));
+    }
+
+    pub fn toggle_no_description_filter(&mut self) {
+        self.update_filter(|filter| filter.no_description = !filter.no_description);
+    }
+
+    /// Cycles the label criterion through no label, then each board label.
+    pub fn cycle_label_filter(&mut self) {
+        let next = match self.filter.labels.first() {
+            None => self.board.labels.first(),
+            Some(current) => self
+                .board
+                .labels
+                .iter()
+                .skip_while(|label| label.id != *current)
+                .nth(1),
+        }
+        .map(|label| label.id);
+
+        self.update_filter(|filter| filter.labels = next.into_iter().collect());
+    }
+
+    /// The label picked in the filter panel with `t`.
+    fn filter_label_id(&self) -> Option<Uuid> {
+        self.filter.labels.first().copied().filter(|id| self.board.get_label(*id).is_some())
+    }
+
+    /// Gives the label picked in the label filter the next palette color.
+    pub fn cycle_filter_label_color(&mut self) {
+        let Some(label) = self.filter_label_id().and_then(|id| self.board.get_label(id)) else {
+            self.status_message = "Pick a label with t first".to_string();
+            return;
+        };
+        let (label_id, color) = (label.id, label.color.next());
+        if self.board.set_label_color(label_id, color) {
+            self.status_message = format!("Label color set to {}", color.as_str());
+        }
+    }
+
+    pub fn start_renaming_label(&mut self) {
+        let Some(label) = self.filter_label_id().and_then(|id| self.board.get_label(id)) else {
+            self.status_message = "Pick a label with t first".to_string();
+            return;
+        };
+        self.label_name_input = label.name.clone();
+        self.input_mode = InputMode::RenamingLabel;
+        self.status_message = "Enter label name".to_string();
+    }
+
+    pub fn cancel_renaming_label(&mut self) {
+        self.label_name_input.clear();
+        self.input_mode = InputMode::Filtering;
+        self.status_message = "Filter tasks".to_string();
+    }
+
+    pub fn finish_renaming_label(&mut self) {
+        let Some(label_id) = self.filter_label_id() else {
+            self.cancel_renaming_label();
+            return;
+        };
+        let name = self.label_name_input.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        if !self.board.rename_label(label_id, &name) {
+            self.status_message = format!("A label called {} already exists", name);
+            return;
+        }
+        self.label_name_input.clear();
+        self.input_mode = InputMode::Filtering;
+        self.status_message = format!("Label renamed to {}", name);
+    }
+
+    /// Deletes the label picked in the label filter, if no task uses it.
+    pub fn delete_filter_label(&mut self) {
+        let Some(label) = self.filter.labels.first().and_then(|id| self.board.get_label(*id)) else {
+            self.status_message = "Pick a label with t first".to_string();
+            return;
+        };
+        let (label_id, name) = (label.id, label.name.clone());
+
+        let uses = self.board.label_uses(label_id);
+        if uses > 0 {
+            self.status_message = format!("Label {} is still used by {} task(s)", name, uses);
+            return;
+        }
+        if self.board.delete_label(label_id) {
+            self.update_filter(|filter| filter.labels.retain(|id| *id != label_id));
+            self.status_message = format!("Label {} deleted", name);
+        }
+    }
+
+    pub fn start_filter_text(&mut self) {
+        self.filter_text_editing = true;
+    }
+
+    pub fn stop_filter_text(&mut self) {
+        self.filter_text_editing = false;
+    }
+
+    pub fn push_filter_char(&mut self, c: char) {
+        self.update_filter(|filter| filter.text.push(c));
+    }
+
+    pub fn pop_filter_char(&mut self) {
+        self.update_filter(|filter| {
+            filter.text.pop();
+        });
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.update_filter(|filter| *filter = TaskFilter::default());
+        self.status_message = "Filter cleared".to_string();
+    }
+
+    /// Applies the next saved view, wrapping back to no filter after the last.
+    pub fn cycle_saved_view(&mut self) {
+        if self.board.views.is_empty() {
+            self.status_message = "No saved views".to_string();
+            return;
+        }
+
+        let next = match self.active_view {
+            None => self.board.views.first(),
+            Some(current) => self
+                .board
+                .views
+                .iter()
+                .skip_while(|view| view.id != current)
+                .nth(1),
+        }
+        .cloned();
+
+        match next {
+            Some(view) => {
+                self.filter = view.filter;
+                self.active_view = Some(view.id);
+                self.status_message = format!("View: {}", view.name);
+            }
+            None => {
+                self.filter = TaskFilter::default();
+                self.active_view = None;
+                self.status_message = "All tasks".to_string();
+            }
+        }
+        self.validate_selection();
+    }
+
+    pub fn start_saving_view(&mut self) {
+        if self.filter.is_empty() {
+            self.status_message = "Nothing to save: the filter is empty".to_string();
+            return;
+        }
+
+        self.view_name_input = self
+            .active_view
+            .and_then(|id| self.board.views.iter().find(|v| v.id == id))
+            .map(|view| view.name.clone())
+            .unwrap_or_default();
+        self.input_mode = InputMode::NamingView;
+        self.status_message = "Enter view name".to_string();
+    }
+
+    pub fn cancel_naming_view(&mut self) {
+        self.view_name_input.clear();
+        self.input_mode = InputMode::Filtering;
+        self.status_message = "Filter tasks".to_string();
+    }
+
+    pub fn finish_naming_view(&mut self) {
+        let name = self.view_name_input.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        let view_id = self
+            .board
+            .save_view(SavedView::new(name.clone(), self.filter.clone()));
+        self.active_view = Some(view_id);
+        self.view_name_input.clear();
+        self.input_mode = InputMode::Filtering;
+        self.status_message = format!("View {} saved", name);
+    }
+
+    pub fn delete_active_view(&mut self) {
+        let Some(view_id) = self.active_view else {
+            self.status_message = "No saved view selected".to_string();
+            return;
+        };
+
+        if self.board.delete_view(view_id) {
+            self.active_view = None;
+            self.status_message = "View deleted".to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    /// Keeps the titles of the cards it was asked to remind about.
+    #[derive(Clone, Default)]
+    struct RecordingNotifier(Rc<RefCell<Vec<String>>>);
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&mut self, _summary: &str, body: &str) {
+            self.0.borrow_mut().push(body.to_string());
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn app_with_due_tasks(tasks: &[(&str, usize, NaiveDate)]) -> (App, RecordingNotifier) {
+        let mut app = App::new();
+        let notifier = RecordingNotifier::default();
+        app.notifier = Box::new(notifier.clone());
+        for (title, column_index, due) in tasks {
+            let column_id = app.board.columns[*column_index].id;
+            let task = Task::new(title.to_string()).with_due_date(Some(to_due_datetime(*due)));
+            app.board.add_task(column_id, task);
+        }
+        (app, notifier)
+    }
+
+    #[test]
+    fn reminders_fire_once_per_card_per_day() {
+        let (mut app, notifier) =
+            app_with_due_tasks(&[("overdue", 0, date(13)), ("today", 1, date(14)), ("later", 0, date(30))]);
+
+        app.check_due_dates(date(14));
+        app.check_due_dates(date(14));
+        assert_eq!(*notifier.0.borrow(), ["overdue", "today"]);
+        assert_eq!(app.due_summary.overdue, 1);
+        assert_eq!(app.due_summary.today, 1);
+
+        app.check_due_dates(date(15));
+        assert_eq!(*notifier.0.borrow(), ["overdue", "today", "overdue", "today"]);
+    }
+
+    #[test]
+    fn cards_in_the_done_column_are_not_reminded() {
+        let (mut app, notifier) = app_with_due_tasks(&[("shipped", 2, date(13))]);
+
+        app.check_due_dates(date(14));
+        assert!(notifier.0.borrow().is_empty());
+        assert_eq!(app.due_summary.overdue, 0);
+    }
+
+    #[test]
+    fn deleting_a_column_respects_a_blocking_wip_limit_on_the_target() {
+        let mut app = App::new();
+        let target_id = app.board.columns[1].id;
+        app.board.set_wip_limit(target_id, Some(1), WipPolicy::Block);
+
+        app.request_delete_column();
+        app.delete_selected_column(true);
+        assert_eq!(app.board.columns.len(), 3);
+        assert!(matches!(app.input_mode, InputMode::DeletingColumn));
+
+        app.board.set_wip_limit(target_id, Some(1), WipPolicy::Warn);
+        app.delete_selected_column(true);
+        assert_eq!(app.board.columns.len(), 2);
+        assert_eq!(app.board.columns[0].tasks.len(), 2);
+        assert!(app.status_message.contains("over its WIP limit"), "{}", app.status_message);
+    }
+
+    #[test]
+    fn purged_tasks_stay_gone_after_undo() {
+        let mut app = App::new();
+        let task_id = app.board.columns[0].tasks[0].id;
+        app.archive_selected_task();
+
+        // The archive screen lists what storage returns; the board already
+        // knows the task it just hid.
+        app.archive = app.board.hidden.clone();
+        app.purge_archived_task();
+        app.undo();
+        app.redo();
+
+        assert!(app.board.get_task(task_id).is_none());
+        assert!(app.board.hidden.is_empty());
+        assert!(app.board.changes.deleted_tasks.contains(&task_id));
+        assert!(!app.board.changes.tasks.contains(&task_id));
+    }
+
+    #[test]
+    fn only_known_hidden_tasks_can_be_restored() {
+        let mut board = Board::new("Test".to_string());
+        let column_id = board.columns[0].id;
+        let task = Task::new("Never hidden".to_string());
+        let restore = Command::Restore { column_id, index: 0, task: task.clone(), state: HiddenState::Archived };
+        assert!(!restore.apply(&mut board));
+
+        board.create_task(column_id, 0, task.clone());
+        assert!(!restore.apply(&mut board));
+        assert_eq!(board.columns[0].tasks.len(), 1);
+    }
+
+    #[test]
+    fn autosave_waits_for_a_quiet_moment_after_the_last_change() {
+        let mut app = App::new();
+        let start = Instant::now();
+        let column_id = app.board.columns[0].id;
+
+        assert!(!app.autosave_due(start));
+        app.board.create_task(column_id, 0, Task::new("Later edit".to_string()));
+        assert!(!app.autosave_due(start + Duration::from_millis(1500)));
+        assert!(!app.autosave_due(start + Duration::from_millis(3000)));
+        assert!(app.autosave_due(start + Duration::from_millis(3500)));
+    }
+
+    #[test]
+    fn autosave_backs_off_after_failed_saves() {
+        let mut app = App::new();
+        let start = Instant::now();
+        assert!(!app.autosave_due(start));
+
+        app.save_failed("disk full".to_string(), start);
+        assert!(!app.autosave_due(start + AUTOSAVE_DELAY * 2 - Duration::from_millis(1)));
+        assert!(app.autosave_due(start + AUTOSAVE_DELAY * 2));
+
+        app.save_failed("disk full".to_string(), start);
+        assert!(!app.autosave_due(start + AUTOSAVE_DELAY * 3));
+        assert!(app.autosave_due(start + AUTOSAVE_DELAY * 4));
+
+        for _ in 0..10 {
+            app.save_failed("disk full".to_string(), start);
+        }
+        assert!(app.autosave_due(start + MAX_SAVE_BACKOFF));
+        assert_eq!(app.save_error.as_deref(), Some("disk full"));
+    }
+
+    #[test]
+    fn search_hits_are_exactly_the_highlighted_tasks() {
+        let mut app = App::new();
+        let column_id = app.board.columns[1].id;
+        app.board.add_task(column_id, Task::new("\u{212A}İİ".to_string()));
+        app.board.add_task(column_id, Task::new("Ship it".to_string()));
+
+        app.search_query = "i".to_string();
+        assert_eq!(app.search_hits().iter().filter(|(column, _)| *column == 1).collect::<Vec<_>>(), [&(1, 1)]);
+        app.search_query = "k".to_string();
+        assert_eq!(app.search_hits().iter().filter(|(column, _)| *column == 1).collect::<Vec<_>>(), [&(1, 0)]);
+    }
+
+    /// A board whose first column holds a (High), b (Low), c (High), d (Low)
+    /// and e (High), filtered down to the High ones.
+    fn app_with_tasks(tasks: &[(&str, Priority)]) -> App {
+        let mut app = App::new();
+        app.board = Board::new("Test".to_string());
+        let column_id = app.board.columns[0].id;
+        for (title, priority) in tasks {
+            app.board.add_task(column_id, Task::new(title.to_string()).with_priority(priority.clone()));
+        }
+        app
+    }
+
+    fn filtered_app() -> App {
+        let mut app = app_with_tasks(&[
+            ("a", Priority::High),
+            ("b", Priority::Low),
+            ("c", Priority::High),
+            ("d", Priority::Low),
+            ("e", Priority::High),
+        ]);
+        app.filter.toggle_priority(Priority::High);
+        app
+    }
+
+    fn column_titles(app: &App, column: usize) -> Vec<&str> {
+        app.board.columns[column].tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    fn selected_title(app: &App) -> Option<&str> {
+        let task_id = app.get_selected_task_id()?;
+        app.board.get_task(task_id).map(|task| task.title.as_str())
+    }
+
+    #[test]
+    fn selection_walks_the_visible_tasks() {
+        let mut app = filtered_app();
+        assert_eq!(selected_title(&app), Some("a"));
+        app.move_selection_down();
+        assert_eq!(selected_title(&app), Some("c"));
+        app.move_selection_down();
+        app.move_selection_down();
+        assert_eq!(selected_title(&app), Some("e"));
+        app.move_selection_up();
+        assert_eq!(selected_title(&app), Some("c"));
+    }
+
+    #[test]
+    fn reordering_under_a_filter_steps_over_hidden_tasks() {
+        let mut app = filtered_app();
+        app.selected_task = 1;
+
+        app.move_selected_task_down();
+        assert_eq!(column_titles(&app, 0), ["a", "b", "d", "e", "c"]);
+        assert_eq!(selected_title(&app), Some("c"));
+
+        app.move_selected_task_up();
+        app.move_selected_task_up();
+        assert_eq!(column_titles(&app, 0), ["c", "a", "b", "d", "e"]);
+        assert_eq!(selected_title(&app), Some("c"));
+
+        app.undo();
+        app.undo();
+        assert_eq!(column_titles(&app, 0), ["a", "b", "d", "e", "c"]);
+    }
+
+    #[test]
+    fn moving_and_archiving_under_a_filter_take_the_selected_task() {
+        let mut app = filtered_app();
+        app.selected_task = 2;
+        app.start_moving_task();
+        app.move_target_right();
+        app.confirm_move_task();
+        assert_eq!(column_titles(&app, 0), ["a", "b", "c", "d"]);
+        assert_eq!(column_titles(&app, 1), ["e"]);
+        // The selection falls back onto the last visible task
+        assert_eq!(selected_title(&app), Some("c"));
+
+        app.archive_selected_task();
+        assert_eq!(column_titles(&app, 0), ["a", "b", "d"]);
+    }
+
+    #[test]
+    fn reordering_stops_at_the_ends_and_undo_restores_the_order() {
+        let mut app = app_with_tasks(&[("a", Priority::Low), ("b", Priority::Low), ("c", Priority::Low)]);
+        app.selected_task = 1;
+
+        app.move_selected_task_down();
+        app.move_selected_task_down();
+        assert_eq!(column_titles(&app, 0), ["a", "c", "b"]);
+        assert_eq!(app.selected_task, 2);
+
+        app.move_selected_task_up();
+        app.move_selected_task_up();
+        app.move_selected_task_up();
+        assert_eq!(column_titles(&app, 0), ["b", "a", "c"]);
+        assert_eq!(app.selected_task, 0);
+
+        app.undo();
+        assert_eq!(column_titles(&app, 0), ["a", "b", "c"]);
+        assert_eq!(selected_title(&app), Some("b"));
+        app.undo();
+        app.undo();
+        assert_eq!(column_titles(&app, 0), ["a", "b", "c"]);
+        assert_eq!(selected_title(&app), Some("b"));
+        app.undo();
+        assert_eq!(app.status_message, "Nothing to undo");
+
+        app.redo();
+        assert_eq!(column_titles(&app, 0), ["a", "c", "b"]);
+    }
+
+    fn app_with_full_column(policy: WipPolicy) -> App {
+        let mut app = app_with_tasks(&[("a", Priority::Low), ("b", Priority::Low)]);
+        let column_id = app.board.columns[1].id;
+        app.board.add_task(column_id, Task::new("x".to_string()));
+        app.board.columns[1].wip_limit = Some(1);
+        app.board.columns[1].wip_policy = policy;
+        app
+    }
+
+    #[test]
+    fn blocking_wip_limit_refuses_moves_into_a_full_column() {
+        let mut app = app_with_full_column(WipPolicy::Block);
+        app.start_moving_task();
+        app.move_target_right();
+        app.confirm_move_task();
+        assert_eq!(column_titles(&app, 0), ["a", "b"]);
+        assert_eq!(column_titles(&app, 1), ["x"]);
+        assert_eq!(app.status_message, "In Progress is at its WIP limit (1/1)");
+
+        app.start_moving_task();
+        app.drop_moving_task(1, Some(0));
+        assert_eq!(column_titles(&app, 1), ["x"]);
+        assert_eq!(app.status_message, "In Progress is at its WIP limit (1/1)");
+    }
+
+    #[test]
+    fn warning_wip_limit_allows_the_move_and_says_so() {
+        let mut app = app_with_full_column(WipPolicy::Warn);
+        app.start_moving_task();
+        app.move_target_right();
+        app.confirm_move_task();
+        assert_eq!(column_titles(&app, 0), ["b"]);
+        assert_eq!(column_titles(&app, 1), ["x", "a"]);
+        assert_eq!(
+            app.status_message,
+            "Task moved to In Progress - In Progress is over its WIP limit (2/1)"
+        );
+    }
+
+    #[test]
+    fn moves_within_a_column_ignore_its_wip_limit() {
+        let mut app = app_with_full_column(WipPolicy::Block);
+        // Over the limit already, as after lowering it
+        let column_id = app.board.columns[1].id;
+        app.board.add_task(column_id, Task::new("y".to_string()));
+        app.selected_column = 1;
+
+        app.move_selected_task_down();
+        assert_eq!(column_titles(&app, 1), ["y", "x"]);
+
+        app.start_moving_task();
+        app.drop_moving_task(1, None);
+        assert_eq!(column_titles(&app, 1), ["y", "x"]);
+        app.start_moving_task();
+        app.drop_moving_task(1, Some(0));
+        assert_eq!(column_titles(&app, 1), ["x", "y"]);
+        assert_eq!(app.status_message, "Task reordered in In Progress");
+    }
+
+    #[test]
+    fn blank_descriptions_are_stored_as_none() {
+        let mut app = app_with_tasks(&[]);
+        for (title, description) in [("blank", "  \n"), ("written", "notes")] {
+            app.start_adding_task();
+            app.edit_state.title = TextEditor::new(title);
+            app.edit_state.description = TextEditor::new(description);
+            app.finish_adding_task();
+        }
+        let descriptions: Vec<_> = app.board.columns[0].tasks.iter().map(|task| task.description.as_deref()).collect();
+        assert_eq!(descriptions, [None, Some("notes")]);
+
+        app.selected_task = 1;
+        app.start_editing_task();
+        app.edit_state.description = TextEditor::new("");
+        app.finish_editing_task();
+        assert_eq!(app.board.columns[0].tasks[1].description, None);
+    }
+}
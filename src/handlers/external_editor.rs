@@ -0,0 +1,249 @@
+use std::{env, fs, io, process::Command};
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::{Priority, due_date::parse_due_date};
+
+/// The editable parts of a task as exchanged with `$VISUAL`/`$EDITOR`: a
+/// Markdown file whose front matter holds the fields and whose body is the
+/// description.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskDocument {
+    pub title: String,
+    pub priority: Priority,
+    pub due_date: Option<NaiveDate>,
+    pub labels: Vec<String>,
+    pub description: String,
+}
+
+impl TaskDocument {
+    pub fn to_markdown(&self) -> String {
+        let due_date = self
+            .due_date
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        format!(
+            "---\ntitle: {}\npriority: {}\ndue: {}\nlabels: {}\n---\n\n{}\n",
+            self.title,
+            self.priority,
+            due_date,
+            self.labels.join(", "),
+            self.description.trim_end(),
+        )
+    }
+
+    /// Reads a document back. `today` anchors relative due dates such as
+    /// `tomorrow`. Errors name the offending line or field.
+    pub fn parse(text: &str, today: NaiveDate) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        if !matches!(lines.next(), Some((_, line)) if line.trim() == "---") {
+            return Err("Missing front matter: the file must start with ---".to_string());
+        }
+
+        let mut title = None;
+        let mut priority = Priority::default();
+        let mut due_date = None;
+        let mut labels = Vec::new();
+        let mut closed = false;
+
+        for (index, line) in lines.by_ref() {
+            if line.trim() == "---" {
+                closed = true;
+                break;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(format!("Line {}: expected 'field: value'", index + 1));
+            };
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "title" => title = Some(value.to_string()),
+                "priority" => priority = parse_priority(value)?,
+                "due" => {
+                    due_date = parse_due_date(value, today).map_err(|e| format!("Due date: {}", e))?;
+                }
+                "labels" => {
+                    labels = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(String::from)
+                        .collect();
+                }
+                other => return Err(format!("Line {}: unknown field '{}'", index + 1, other)),
+            }
+        }
+        if !closed {
+            return Err("Front matter is not closed with ---".to_string());
+        }
+
+        let title = title.unwrap_or_default();
+        if title.is_empty() {
+            return Err("The title must not be empty".to_string());
+        }
+        let body: Vec<&str> = lines.map(|(_, line)| line).collect();
+        let description = body.join("\n").trim_matches('\n').trim_end().to_string();
+
+        Ok(Self {
+            title,
+            priority,
+            due_date,
+            labels,
+            description,
+        })
+    }
+}
+
+fn parse_priority(value: &str) -> Result<Priority, String> {
+    match value.to_lowercase().as_str() {
+        "" | "medium" => Ok(Priority::Medium),
+        "low" => Ok(Priority::Low),
+        "high" => Ok(Priority::High),
+        "critical" => Ok(Priority::Critical),
+        _ => Err(format!("Unknown priority '{}' (use Low, Medium, High or Critical)", value)),
+    }
+}
+
+/// Writes `contents` to a temporary Markdown file, opens it in `$VISUAL`,
+/// `$EDITOR` or `vi`, and returns the file as saved once the editor exits.
+/// The caller must hand over the terminal first.
+pub fn edit(contents: &str) -> io::Result<String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    // Allow editors configured with arguments, e.g. `code --wait`
+    let mut parts = split_command(&editor).into_iter();
+    let program = parts.next().unwrap_or_else(|| "vi".to_string());
+
+    let path = env::temp_dir().join(format!("kanban-{}.md", Uuid::new_v4()));
+    fs::write(&path, contents)?;
+    let status = Command::new(&program).args(parts).arg(&path).status();
+    let edited = match status {
+        Ok(status) if status.success() => fs::read_to_string(&path),
+        Ok(status) => Err(io::Error::other(format!("{} exited with {}", program, status))),
+        Err(e) => Err(io::Error::new(e.kind(), format!("Could not start {}: {}", program, e))),
+    };
+    let _ = fs::remove_file(&path);
+    edited
+}
+
+/// Splits an editor command into words the way a shell would for simple
+/// cases: single and double quotes group words, and a backslash outside
+/// single quotes escapes the next character.
+fn split_command(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => word.push(c),
+            (_, '\\') => {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+    }
+
+    fn document() -> TaskDocument {
+        TaskDocument {
+            title: "Fix login: redirect".to_string(),
+            priority: Priority::High,
+            due_date: NaiveDate::from_ymd_opt(2026, 11, 3),
+            labels: vec!["bug".to_string(), "frontend".to_string()],
+            description: "Steps:\n\n1. log in\n---\nnot front matter".to_string(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_markdown() {
+        let document = document();
+        assert_eq!(TaskDocument::parse(&document.to_markdown(), today()), Ok(document));
+    }
+
+    #[test]
+    fn empty_fields_round_trip() {
+        let document = TaskDocument {
+            title: "Plain".to_string(),
+            priority: Priority::Medium,
+            due_date: None,
+            labels: Vec::new(),
+            description: String::new(),
+        };
+        assert_eq!(TaskDocument::parse(&document.to_markdown(), today()), Ok(document));
+    }
+
+    #[test]
+    fn relative_due_dates_use_today() {
+        let parsed = TaskDocument::parse("---\ntitle: t\ndue: tomorrow\n---\n", today()).unwrap();
+        assert_eq!(parsed.due_date, NaiveDate::from_ymd_opt(2026, 10, 15));
+    }
+
+    #[test]
+    fn parse_errors_name_the_problem() {
+        let cases = [
+            ("title: t\n", "Missing front matter"),
+            ("---\ntitle: t\n", "not closed"),
+            ("---\ntitle: t\njust text\n---\n", "Line 3: expected"),
+            ("---\ntitle: t\nowner: me\n---\n", "Line 3: unknown field 'owner'"),
+            ("---\ntitle: t\npriority: urgent\n---\n", "Unknown priority 'urgent'"),
+            ("---\ntitle: t\ndue: someday\n---\n", "Due date:"),
+            ("---\ntitle:\n---\n", "title must not be empty"),
+        ];
+        for (text, expected) in cases {
+            let error = TaskDocument::parse(text, today()).unwrap_err();
+            assert!(error.contains(expected), "{:?} gave {:?}", text, error);
+        }
+    }
+
+    #[test]
+    fn editor_commands_split_like_a_shell() {
+        assert_eq!(split_command("code --wait"), ["code", "--wait"]);
+        assert_eq!(
+            split_command("\"/opt/My Editor/bin/edit\" -w"),
+            ["/opt/My Editor/bin/edit", "-w"]
+        );
+        assert_eq!(split_command("'/a b/vim' -c 'set tw=72'"), ["/a b/vim", "-c", "set tw=72"]);
+        assert_eq!(split_command("/a\\ b/nano"), ["/a b/nano"]);
+        assert_eq!(split_command("ed ''"), ["ed", ""]);
+        assert!(split_command("   ").is_empty());
+    }
+}
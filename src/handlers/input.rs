@@ -5,6 +5,9 @@ use crate::{
     models::Priority,
 };
 
+/// Lines scrolled by PageUp/PageDown in the task detail view.
+const DETAIL_PAGE: u16 = 10;
+
 pub fn handle_key_events(key_event: KeyEvent, app: &mut App) {
     // Only handle key press events, not release events to avoid duplicate input
     if key_event.kind != KeyEventKind::Press {
@@ -15,6 +18,19 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) {
         InputMode::Normal => handle_normal_mode(key_event, app),
         InputMode::AddingTask | InputMode::Editing => handle_input_mode(key_event, app),
         InputMode::MovingTask => handle_moving_mode(key_event, app),
+        InputMode::BoardPicker => handle_board_picker_mode(key_event, app),
+        InputMode::NamingBoard => handle_naming_board_mode(key_event, app),
+        InputMode::ManagingColumns => handle_managing_columns_mode(key_event, app),
+        InputMode::NamingColumn => handle_naming_column_mode(key_event, app),
+        InputMode::SettingWipLimit => handle_setting_wip_limit_mode(key_event, app),
+        InputMode::DeletingColumn => handle_deleting_column_mode(key_event, app),
+        InputMode::Searching => handle_searching_mode(key_event, app),
+        InputMode::Filtering => handle_filtering_mode(key_event, app),
+        InputMode::NamingView => handle_naming_view_mode(key_event, app),
+        InputMode::RenamingLabel => handle_renaming_label_mode(key_event, app),
+        InputMode::Archive => handle_archive_mode(key_event, app),
+        InputMode::TaskDetail => handle_task_detail_mode(key_event, app),
+        InputMode::Metrics => handle_metrics_mode(key_event, app),
     }
 }
 
@@ -39,6 +55,36 @@ fn handle_normal_mode(key_event: KeyEvent, app: &mut App) {
             app.move_selection_up();
         }
         
+        // Reorder within column
+        KeyCode::Char('J') => {
+            app.move_selected_task_down();
+        }
+        KeyCode::Char('K') => {
+            app.move_selected_task_up();
+        }
+        
+        // Undo/redo
+        KeyCode::Char('r') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.redo();
+        }
+        KeyCode::Char('u') | KeyCode::Char('U') => {
+            app.undo();
+        }
+
+        // Search; while a search is active n/N cycle through its matches
+        KeyCode::Char('/') => {
+            app.start_search();
+        }
+        KeyCode::Char('n') if app.is_search_active() => {
+            app.search_next();
+        }
+        KeyCode::Char('N') if app.is_search_active() => {
+            app.search_prev();
+        }
+        KeyCode::Esc if app.is_search_active() => {
+            app.clear_search();
+        }
+
         // Task operations
         KeyCode::Char('n') | KeyCode::Char('N') => {
             app.start_adding_task();
@@ -46,9 +92,31 @@ fn handle_normal_mode(key_event: KeyEvent, app: &mut App) {
         KeyCode::Enter => {
             app.start_editing_task();
         }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            app.request_external_edit();
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            app.open_task_detail();
+        }
         KeyCode::Char('d') | KeyCode::Char('D') => {
             app.delete_selected_task();
         }
+
+        // Archive and trash
+        KeyCode::Char('a') => {
+            app.archive_selected_task();
+        }
+        KeyCode::Char('A') => {
+            app.archive_selected_column();
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            app.open_archive();
+        }
+
+        // Flow metrics
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.open_metrics();
+        }
         
         // Move task between columns
         KeyCode::Char('m') => {
@@ -57,101 +125,154 @@ fn handle_normal_mode(key_event: KeyEvent, app: &mut App) {
         KeyCode::Char('M') => {
             app.move_task_to_prev_column();
         }
+
+        // Filters and saved views
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            app.open_filter_panel();
+        }
+        KeyCode::Char('v') | KeyCode::Char('V') => {
+            app.cycle_saved_view();
+        }
+
+        // Boards and columns
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            app.open_board_picker();
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') => {
+            app.start_managing_columns();
+        }
         
         _ => {}
     }
 }
 
 fn handle_input_mode(key_event: KeyEvent, app: &mut App) {
+    let in_checklist = matches!(app.edit_state.selected_field, EditField::Checklist);
+    let in_blockers = matches!(app.edit_state.selected_field, EditField::Blockers);
+    let in_description = matches!(app.edit_state.selected_field, EditField::Description);
+    let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+    // Alt-↑/↓ select list items, so ←/→ move the cursor as in every field
+    let alt = key_event.modifiers.contains(KeyModifiers::ALT);
+
     match key_event.code {
-        KeyCode::Enter => {
-            // Only finish adding/editing if title is not empty or just whitespace
-            if !app.edit_state.title.trim().is_empty() {
-                match app.input_mode {
-                    InputMode::AddingTask => app.finish_adding_task(),
-                    InputMode::Editing => app.finish_editing_task(),
-                    _ => {}
-                }
-            }
+        // In the checklist field Enter adds the typed item instead of submitting
+        KeyCode::Enter if in_checklist && !app.edit_state.checklist_input.text().trim().is_empty() => {
+            app.edit_state.add_checklist_item();
+        }
+        KeyCode::Up if in_checklist && alt => {
+            app.edit_state.select_prev_checklist_item();
+        }
+        KeyCode::Down if in_checklist && alt => {
+            app.edit_state.select_next_checklist_item();
+        }
+        // Likewise Enter picks the highlighted task while searching for a blocker
+        KeyCode::Enter if in_blockers && !app.edit_state.blocker_query.text().trim().is_empty() => {
+            app.add_selected_blocker();
+        }
+        KeyCode::Up if in_blockers && alt => {
+            app.select_prev_blocker();
+        }
+        KeyCode::Down if in_blockers && alt => {
+            app.select_next_blocker();
         }
+        // Descriptions can span several lines; Ctrl-S submits from there
+        KeyCode::Enter if in_description => {
+            app.edit_state.description.insert_char('\n');
+        }
+        KeyCode::Enter => submit_task_popup(app),
         KeyCode::Esc => {
             app.cancel_input();
         }
-        KeyCode::Char(c) => {
-            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
-                match c {
-                    'c' => app.cancel_input(),
-                    _ => {}
-                }
-            } else {
-                match app.edit_state.selected_field {
-                    EditField::Title => {
-                        match c {
-                            _ => {
-                                app.edit_state.title.push(c);
-                            }
-                        }
-                    }
-                    EditField::Description => {
-                        match c {
-                            _ => {
-                                app.edit_state.description.push(c);
-                            }
-                        }
-                    }
-                    EditField::Priority => {
-                        match c {
-                            '+' | '=' => {
-                                app.edit_state.priority = match app.edit_state.priority {
-                                    Priority::Low => Priority::Medium,
-                                    Priority::Medium => Priority::High,
-                                    Priority::High => Priority::Critical,
-                                    Priority::Critical => Priority::Critical,
-                                };
-                            }
-                            '-' => {
-                                app.edit_state.priority = match app.edit_state.priority {
-                                    Priority::Critical => Priority::High,
-                                    Priority::High => Priority::Medium,
-                                    Priority::Medium => Priority::Low,
-                                    Priority::Low => Priority::Low,
-                                };
-                            }
-                            _ => {}
-                        }
-                    }
+        KeyCode::Char(c) if ctrl && matches!(c, 'c' | 's' | 'o' | 'x' | 'd') => match c {
+            'c' => app.cancel_input(),
+            's' => submit_task_popup(app),
+            'o' => app.request_external_edit(),
+            'x' if in_checklist => app.edit_state.toggle_checklist_item(),
+            'd' if in_checklist => app.edit_state.delete_checklist_item(),
+            'd' if in_blockers => app.edit_state.remove_selected_blocker(),
+            'd' => {
+                if let Some(editor) = app.edit_state.selected_editor_mut() {
+                    editor.delete_after();
                 }
             }
-        }
-        KeyCode::Backspace => {
-            match app.edit_state.selected_field {
-                EditField::Title => {
-                    app.edit_state.title.pop();
-                }
-                EditField::Description => {
-                    app.edit_state.description.pop();
+            _ => {}
+        },
+        KeyCode::Char(c) if matches!(app.edit_state.selected_field, EditField::Priority) => {
+            match c {
+                '+' | '=' => {
+                    app.edit_state.priority = match app.edit_state.priority {
+                        Priority::Low => Priority::Medium,
+                        Priority::Medium => Priority::High,
+                        Priority::High => Priority::Critical,
+                        Priority::Critical => Priority::Critical,
+                    };
                 }
-                EditField::Priority => {
-                    // Priority field doesn't support backspace
+                '-' => {
+                    app.edit_state.priority = match app.edit_state.priority {
+                        Priority::Critical => Priority::High,
+                        Priority::High => Priority::Medium,
+                        Priority::Medium => Priority::Low,
+                        Priority::Low => Priority::Low,
+                    };
                 }
+                _ => {}
             }
         }
         KeyCode::Tab => {
             app.move_edit_field_next();
         }
+        // Up/Down move between description lines before leaving the field
         KeyCode::Down => {
-            app.move_edit_field_next();
+            if !(in_description && app.edit_state.description.move_down()) {
+                app.move_edit_field_next();
+            }
         }
         KeyCode::Up => {
-            app.move_edit_field_prev();
+            if !(in_description && app.edit_state.description.move_up()) {
+                app.move_edit_field_prev();
+            }
         }
+        _ => {
+            let edited = app
+                .edit_state
+                .selected_editor_mut()
+                .is_some_and(|editor| editor.handle_key(key_event));
+            if edited && in_blockers {
+                app.edit_state.selected_candidate = 0;
+            }
+        }
+    }
+}
+
+/// Inserts pasted text into the focused field of the task popup.
+pub fn handle_paste(text: &str, app: &mut App) {
+    if !matches!(app.input_mode, InputMode::AddingTask | InputMode::Editing) {
+        return;
+    }
+    let in_blockers = matches!(app.edit_state.selected_field, EditField::Blockers);
+    if let Some(editor) = app.edit_state.selected_editor_mut() {
+        editor.insert_str(text);
+        if in_blockers {
+            app.edit_state.selected_candidate = 0;
+        }
+    }
+}
+
+/// Finishes adding or editing, unless the title is empty or just whitespace.
+fn submit_task_popup(app: &mut App) {
+    if app.edit_state.title.text().trim().is_empty() {
+        return;
+    }
+    match app.input_mode {
+        InputMode::AddingTask => app.finish_adding_task(),
+        InputMode::Editing => app.finish_editing_task(),
         _ => {}
     }
 }
 
 fn handle_moving_mode(key_event: KeyEvent, app: &mut App) {
     match key_event.code {
-        KeyCode::Char('m') => {
+        KeyCode::Char('m') | KeyCode::Enter => {
             app.confirm_move_task();
         }
         KeyCode::Esc => {
@@ -165,4 +286,401 @@ fn handle_moving_mode(key_event: KeyEvent, app: &mut App) {
         }
         _ => {}
     }
-}
\ No newline at end of file
+}
+
+fn handle_board_picker_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.close_board_picker();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.move_board_selection_down();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.move_board_selection_up();
+        }
+        KeyCode::Enter => {
+            app.switch_to_selected_board();
+        }
+        KeyCode::Char('n') => {
+            app.start_creating_board();
+        }
+        KeyCode::Char('r') => {
+            app.start_renaming_board();
+        }
+        KeyCode::Char('d') => {
+            app.delete_selected_board();
+        }
+        _ => {}
+    }
+}
+
+fn handle_naming_board_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Enter => {
+            app.finish_naming_board();
+        }
+        KeyCode::Esc => {
+            app.cancel_naming_board();
+        }
+        KeyCode::Char(c) => {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                if c == 'c' {
+                    app.cancel_naming_board();
+                }
+            } else {
+                app.board_name_input.push(c);
+            }
+        }
+        KeyCode::Backspace => {
+            app.board_name_input.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_managing_columns_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('c') => {
+            app.stop_managing_columns();
+        }
+        KeyCode::Char('h') | KeyCode::Left => {
+            app.move_selection_left();
+        }
+        KeyCode::Char('l') | KeyCode::Right => {
+            app.move_selection_right();
+        }
+        KeyCode::Char('H') | KeyCode::Char('<') => {
+            app.move_column_left();
+        }
+        KeyCode::Char('L') | KeyCode::Char('>') => {
+            app.move_column_right();
+        }
+        KeyCode::Char('a') | KeyCode::Char('n') => {
+            app.start_adding_column();
+        }
+        KeyCode::Char('r') | KeyCode::Enter => {
+            app.start_renaming_column();
+        }
+        KeyCode::Char('d') => {
+            app.request_delete_column();
+        }
+        KeyCode::Char('w') => {
+            app.start_setting_wip_limit();
+        }
+        KeyCode::Char('p') => {
+            app.toggle_wip_policy();
+        }
+        _ => {}
+    }
+}
+
+fn handle_naming_column_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Enter => {
+            app.finish_naming_column();
+        }
+        KeyCode::Esc => {
+            app.cancel_naming_column();
+        }
+        KeyCode::Char(c) => {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                if c == 'c' {
+                    app.cancel_naming_column();
+                }
+            } else {
+                app.column_name_input.push(c);
+            }
+        }
+        KeyCode::Backspace => {
+            app.column_name_input.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_setting_wip_limit_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Enter => {
+            app.finish_setting_wip_limit();
+        }
+        KeyCode::Esc => {
+            app.cancel_setting_wip_limit();
+        }
+        KeyCode::Char(c) => {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                if c == 'c' {
+                    app.cancel_setting_wip_limit();
+                }
+            } else if c.is_ascii_digit() {
+                app.wip_limit_input.push(c);
+            }
+        }
+        KeyCode::Backspace => {
+            app.wip_limit_input.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_deleting_column_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Char('m') => {
+            app.delete_selected_column(true);
+        }
+        KeyCode::Char('x') => {
+            app.delete_selected_column(false);
+        }
+        KeyCode::Esc | KeyCode::Char('n') => {
+            app.cancel_delete_column();
+        }
+        _ => {}
+    }
+}
+
+fn handle_searching_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Enter => {
+            app.confirm_search();
+        }
+        KeyCode::Esc => {
+            app.clear_search();
+        }
+        KeyCode::Char(c) => {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                if c == 'c' {
+                    app.clear_search();
+                }
+            } else {
+                app.push_search_char(c);
+            }
+        }
+        KeyCode::Backspace => {
+            app.pop_search_char();
+        }
+        _ => {}
+    }
+}
+
+fn handle_filtering_mode(key_event: KeyEvent, app: &mut App) {
+    if app.filter_text_editing {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                app.stop_filter_text();
+            }
+            KeyCode::Char(c) => {
+                if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                    if c == 'c' {
+                        app.stop_filter_text();
+                    }
+                } else {
+                    app.push_filter_char(c);
+                }
+            }
+            KeyCode::Backspace => {
+                app.pop_filter_char();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('f') => {
+            app.close_filter_panel();
+        }
+        KeyCode::Char('c') => {
+            app.toggle_priority_filter(Priority::Critical);
+        }
+        KeyCode::Char('h') => {
+            app.toggle_priority_filter(Priority::High);
+        }
+        KeyCode::Char('m') => {
+            app.toggle_priority_filter(Priority::Medium);
+        }
+        KeyCode::Char('l') => {
+            app.toggle_priority_filter(Priority::Low);
+        }
+        KeyCode::Char('d') => {
+            app.cycle_due_filter();
+        }
+        KeyCode::Char('e') => {
+            app.toggle_no_description_filter();
+        }
+        KeyCode::Char('t') => {
+            app.cycle_label_filter();
+        }
+        KeyCode::Char('T') => {
+            app.delete_filter_label();
+        }
+        KeyCode::Char('C') => {
+            app.cycle_filter_label_color();
+        }
+        KeyCode::Char('R') => {
+            app.start_renaming_label();
+        }
+        KeyCode::Char('/') => {
+            app.start_filter_text();
+        }
+        KeyCode::Char('x') => {
+            app.clear_filter();
+        }
+        KeyCode::Char('s') => {
+            app.start_saving_view();
+        }
+        KeyCode::Char('v') | KeyCode::Tab => {
+            app.cycle_saved_view();
+        }
+        KeyCode::Char('D') => {
+            app.delete_active_view();
+        }
+        _ => {}
+    }
+}
+
+fn handle_naming_view_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Enter => {
+            app.finish_naming_view();
+        }
+        KeyCode::Esc => {
+            app.cancel_naming_view();
+        }
+        KeyCode::Char(c) => {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                if c == 'c' {
+                    app.cancel_naming_view();
+                }
+            } else {
+                app.view_name_input.push(c);
+            }
+        }
+        KeyCode::Backspace => {
+            app.view_name_input.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_renaming_label_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Enter => {
+            app.finish_renaming_label();
+        }
+        KeyCode::Esc => {
+            app.cancel_renaming_label();
+        }
+        KeyCode::Char(c) => {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                if c == 'c' {
+                    app.cancel_renaming_label();
+                }
+            } else {
+                app.label_name_input.push(c);
+            }
+        }
+        KeyCode::Backspace => {
+            app.label_name_input.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_archive_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.move_archive_selection_down();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.move_archive_selection_up();
+        }
+        KeyCode::Char('r') | KeyCode::Enter => {
+            app.restore_archived_task();
+        }
+        KeyCode::Char('P') => {
+            app.purge_archived_task();
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('t') => {
+            app.close_archive();
+        }
+        _ => {}
+    }
+}
+
+fn handle_task_detail_mode(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Enter | KeyCode::Char('e') => {
+            app.close_task_detail();
+            app.start_editing_task();
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') => {
+            app.close_task_detail();
+        }
+        KeyCode::Down | KeyCode::Char('j') => app.scroll_detail_down(1),
+        KeyCode::Up | KeyCode::Char('k') => app.scroll_detail_up(1),
+        KeyCode::PageDown | KeyCode::Char(' ') => app.scroll_detail_down(DETAIL_PAGE),
+        KeyCode::PageUp => app.scroll_detail_up(DETAIL_PAGE),
+        KeyCode::Home | KeyCode::Char('g') => app.detail_scroll = 0,
+        // Rendering clamps this to the last page
+        KeyCode::End | KeyCode::Char('G') => app.detail_scroll = u16::MAX,
+        _ => {}
+    }
+}
+
+fn handle_metrics_mode(key_event: KeyEvent, app: &mut App) {
+    if let KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('s') = key_event.code {
+        app.close_metrics();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+        handle_key_events(KeyEvent::new(code, modifiers), app);
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn arrows_move_the_cursor_in_the_checklist_input_and_alt_arrows_select_items() {
+        let mut app = App::new();
+        app.start_adding_task();
+        app.edit_state.selected_field = EditField::Checklist;
+        for item in ["first", "second"] {
+            type_text(&mut app, item);
+            press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        }
+        assert_eq!(app.edit_state.selected_item, 1);
+
+        type_text(&mut app, "ac");
+        press(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        type_text(&mut app, "b");
+        assert_eq!(app.edit_state.checklist_input.text(), "abc");
+        assert_eq!(app.edit_state.selected_item, 1);
+
+        press(&mut app, KeyCode::Up, KeyModifiers::ALT);
+        assert_eq!(app.edit_state.selected_item, 0);
+        press(&mut app, KeyCode::Down, KeyModifiers::ALT);
+        assert_eq!(app.edit_state.selected_item, 1);
+        assert!(matches!(app.edit_state.selected_field, EditField::Checklist));
+    }
+
+    #[test]
+    fn arrows_move_the_cursor_in_the_blocker_search() {
+        let mut app = App::new();
+        app.start_adding_task();
+        app.edit_state.selected_field = EditField::Blockers;
+
+        type_text(&mut app, "Rst");
+        press(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        type_text(&mut app, "u");
+        assert_eq!(app.edit_state.blocker_query.text(), "Rust");
+    }
+}
@@ -0,0 +1,244 @@
+use std::{error::Error, fmt};
+
+use rusqlite::Connection;
+
+/// Ordered schema migrations. Step `i` upgrades a database from
+/// `user_version` `i` to `i + 1`; steps are never edited once released,
+/// schema changes always go into a new step at the end.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial schema. Databases created before versioning existed report
+    // user_version 0 but already have these tables, hence IF NOT EXISTS.
+    "CREATE TABLE IF NOT EXISTS boards (
+        id TEXT PRIMARY KEY,
+        title TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS columns (
+        id TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        board_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        FOREIGN KEY(board_id) REFERENCES boards(id)
+    );
+    CREATE TABLE IF NOT EXISTS tasks (
+        id TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        description TEXT,
+        due_date TEXT,
+        priority TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        column_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        FOREIGN KEY(column_id) REFERENCES columns(id)
+    );",
+    // 2: board-level labels and the many-to-many link to tasks.
+    "CREATE TABLE labels (
+        id TEXT PRIMARY KEY,
+        board_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        color TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        FOREIGN KEY(board_id) REFERENCES boards(id) ON DELETE CASCADE
+    );
+    CREATE TABLE task_labels (
+        task_id TEXT NOT NULL,
+        label_id TEXT NOT NULL,
+        PRIMARY KEY(task_id, label_id),
+        FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+        FOREIGN KEY(label_id) REFERENCES labels(id) ON DELETE CASCADE
+    );",
+    // 3: named filter views; the filter itself is stored as JSON.
+    "CREATE TABLE views (
+        id TEXT PRIMARY KEY,
+        board_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        filter TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        FOREIGN KEY(board_id) REFERENCES boards(id) ON DELETE CASCADE
+    );",
+    // 4: archived and trashed tasks stay in the table but off the board.
+    "ALTER TABLE tasks ADD COLUMN archived_at TEXT;
+    ALTER TABLE tasks ADD COLUMN deleted_at TEXT;",
+    // 5: append-only task activity log. Rows outlive purged tasks, so there
+    // is no foreign key to tasks.
+    "CREATE TABLE task_events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        board_id TEXT NOT NULL,
+        task_id TEXT NOT NULL,
+        at TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        field TEXT,
+        old_value TEXT,
+        new_value TEXT,
+        FOREIGN KEY(board_id) REFERENCES boards(id) ON DELETE CASCADE
+    );
+    CREATE INDEX task_events_task ON task_events(task_id, id);",
+    // 6: optional per-column WIP limits.
+    "ALTER TABLE columns ADD COLUMN wip_limit INTEGER;
+    ALTER TABLE columns ADD COLUMN wip_policy TEXT NOT NULL DEFAULT 'warn';",
+    // 7: ordered checklist items belonging to a task.
+    "CREATE TABLE checklist_items (
+        id TEXT PRIMARY KEY,
+        task_id TEXT NOT NULL,
+        text TEXT NOT NULL,
+        done INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+    );",
+    // 8: "blocked by" links between tasks. Blockers have no foreign key so a
+    // task can be written before the tasks it waits for in the same save;
+    // storage removes the links when a blocker is deleted.
+    "CREATE TABLE task_dependencies (
+        task_id TEXT NOT NULL,
+        blocker_id TEXT NOT NULL,
+        PRIMARY KEY (task_id, blocker_id),
+        FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+    );
+    CREATE INDEX task_dependencies_blocker ON task_dependencies(blocker_id);",
+];
+
+/// Schema version this build reads and writes.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+#[derive(Debug)]
+pub enum MigrationError {
+    Sql(rusqlite::Error),
+    /// The database was written by a newer build; opening it could lose data.
+    NewerSchema { found: u32, supported: u32 },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Sql(e) => write!(f, "Migration failed: {}", e),
+            MigrationError::NewerSchema { found, supported } => write!(
+                f,
+                "Database schema version {} is newer than this version of kanban supports ({}). Please upgrade kanban.",
+                found, supported
+            ),
+        }
+    }
+}
+
+impl Error for MigrationError {}
+
+impl From<rusqlite::Error> for MigrationError {
+    fn from(e: rusqlite::Error) -> Self {
+        MigrationError::Sql(e)
+    }
+}
+
+pub fn schema_version(conn: &Connection) -> rusqlite::Result<u32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+/// Brings the database up to `SCHEMA_VERSION`.
+pub fn migrate(conn: &mut Connection) -> Result<(), MigrationError> {
+    migrate_to(conn, SCHEMA_VERSION)
+}
+
+fn migrate_to(conn: &mut Connection, target: u32) -> Result<(), MigrationError> {
+    let version = schema_version(conn)?;
+    if version > SCHEMA_VERSION {
+        return Err(MigrationError::NewerSchema {
+            found: version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+
+    // Each step commits together with its version bump, so an interrupted
+    // upgrade resumes from the last completed step.
+    for (step, sql) in MIGRATIONS
+        .iter()
+        .enumerate()
+        .take(target as usize)
+        .skip(version as usize)
+    {
+        let tx = conn.transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", step as u32 + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_names(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn fresh_database_is_migrated_to_latest() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let tables = table_names(&conn);
+        for table in ["boards", "columns", "tasks", "labels", "task_labels", "views", "task_events", "checklist_items", "task_dependencies"] {
+            assert!(tables.iter().any(|name| name == table), "missing table {}", table);
+        }
+    }
+
+    #[test]
+    fn every_step_upgrades_from_the_previous_version() {
+        for start in 0..=SCHEMA_VERSION {
+            let mut conn = Connection::open_in_memory().unwrap();
+            migrate_to(&mut conn, start).unwrap();
+            assert_eq!(schema_version(&conn).unwrap(), start);
+
+            migrate(&mut conn).unwrap();
+            assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        }
+    }
+
+    #[test]
+    fn migrating_twice_is_a_no_op() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn unversioned_legacy_database_keeps_its_data() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE boards (id TEXT PRIMARY KEY, title TEXT NOT NULL);
+             INSERT INTO boards (id, title) VALUES ('b1', 'Legacy');",
+        )
+        .unwrap();
+
+        migrate(&mut conn).unwrap();
+
+        let title: String = conn
+            .query_row("SELECT title FROM boards WHERE id = 'b1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(title, "Legacy");
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn newer_database_is_refused() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1).unwrap();
+
+        match migrate(&mut conn) {
+            Err(MigrationError::NewerSchema { found, supported }) => {
+                assert_eq!(found, SCHEMA_VERSION + 1);
+                assert_eq!(supported, SCHEMA_VERSION);
+            }
+            other => panic!("expected NewerSchema error, got {:?}", other),
+        }
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION + 1);
+    }
+}
@@ -1,2 +1,6 @@
+pub mod external_editor;
 pub mod input;
+pub mod migrations;
+pub mod mouse;
+pub mod notifier;
 pub mod storage;
\ No newline at end of file
@@ -0,0 +1,195 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use crate::app::{App, InputMode};
+
+/// Longest gap between two clicks on a card that still counts as a
+/// double-click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Lines scrolled per wheel step in the task detail view.
+const DETAIL_WHEEL_STEP: u16 = 3;
+
+pub fn handle_mouse_events(mouse: MouseEvent, app: &mut App) {
+    match app.input_mode {
+        InputMode::Normal => handle_board_mouse(mouse, app),
+        InputMode::MovingTask if app.pressed_task.is_some() => handle_drag_mouse(mouse, app),
+        InputMode::TaskDetail => match mouse.kind {
+            MouseEventKind::ScrollUp => app.scroll_detail_up(DETAIL_WHEEL_STEP),
+            MouseEventKind::ScrollDown => app.scroll_detail_down(DETAIL_WHEEL_STEP),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn handle_board_mouse(mouse: MouseEvent, app: &mut App) {
+    let position = app.board_position(mouse.column, mouse.row);
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let Some((column_index, row)) = position else {
+                return;
+            };
+            let task_id = app.select_card(column_index, row);
+            app.pressed_task = task_id;
+
+            let Some(task_id) = task_id else {
+                app.last_click = None;
+                return;
+            };
+            let double_click = app
+                .last_click
+                .is_some_and(|(at, id)| id == task_id && at.elapsed() <= DOUBLE_CLICK);
+            if double_click {
+                app.last_click = None;
+                app.pressed_task = None;
+                app.start_editing_task();
+            } else {
+                app.last_click = Some((Instant::now(), task_id));
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) if app.pressed_task.is_some() => {
+            app.last_click = None;
+            app.start_dragging_task();
+            if let Some((column_index, _)) = position {
+                app.target_column = column_index;
+            }
+        }
+        MouseEventKind::Up(_) => {
+            app.pressed_task = None;
+        }
+        // The wheel walks the selection through the column under the pointer
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            let Some((column_index, _)) = position else {
+                return;
+            };
+            app.select_card(column_index, None);
+            if matches!(mouse.kind, MouseEventKind::ScrollUp) {
+                app.move_selection_up();
+            } else {
+                app.move_selection_down();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_drag_mouse(mouse: MouseEvent, app: &mut App) {
+    let position = app.board_position(mouse.column, mouse.row);
+
+    match mouse.kind {
+        MouseEventKind::Drag(MouseButton::Left) => {
+            if let Some((column_index, _)) = position {
+                app.target_column = column_index;
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            app.pressed_task = None;
+            match position {
+                Some((column_index, row)) => app.drop_moving_task(column_index, row),
+                None => app.cancel_input(),
+            }
+        }
+        _ => {}
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use ratatui::{layout::Rect, widgets::ListState};
+
+    use super::*;
+    use crate::models::{Board, Task};
+
+    /// A board on its second page: "To Do" is off screen, "In Progress"
+    /// spans x 0..30 and "Done" x 30..60, both with cards from y 2 to 7.
+    fn paged_app(in_progress: &[&str], done: &[&str]) -> App {
+        let mut app = App::new();
+        app.board = Board::new("Test".to_string());
+        for (column, titles) in [(1, in_progress), (2, done)] {
+            let column_id = app.board.columns[column].id;
+            for title in titles {
+                app.board.add_task(column_id, Task::new(title.to_string()));
+            }
+        }
+        app.selected_column = 1;
+        app.column_areas = vec![Rect::default(), Rect::new(0, 1, 30, 8), Rect::new(30, 1, 30, 8)];
+        app
+    }
+
+    fn titles(app: &App, column: usize) -> Vec<&str> {
+        app.board.columns[column].tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    fn send(app: &mut App, kind: MouseEventKind, column: u16, row: u16) {
+        handle_mouse_events(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }, app);
+    }
+
+    fn drag(app: &mut App, from: (u16, u16), to: (u16, u16)) {
+        send(app, MouseEventKind::Down(MouseButton::Left), from.0, from.1);
+        send(app, MouseEventKind::Drag(MouseButton::Left), to.0, to.1);
+        send(app, MouseEventKind::Up(MouseButton::Left), to.0, to.1);
+    }
+
+    #[test]
+    fn cells_map_to_columns_and_rows() {
+        let mut app = paged_app(&["a", "b", "c", "d", "e", "f", "g", "h", "i"], &["x"]);
+        let column_id = app.board.columns[1].id;
+        app.column_states.insert(column_id, ListState::default().with_offset(3));
+
+        // The hidden first page takes no cells
+        assert_eq!(app.board_position(0, 0), None);
+        assert_eq!(app.board_position(5, 1), Some((1, None)));
+        assert_eq!(app.board_position(5, 2), Some((1, Some(3))));
+        assert_eq!(app.board_position(29, 7), Some((1, Some(8))));
+        assert_eq!(app.board_position(5, 8), Some((1, None)));
+        assert_eq!(app.board_position(30, 2), Some((2, Some(0))));
+        assert_eq!(app.board_position(45, 6), Some((2, Some(4))));
+        assert_eq!(app.board_position(60, 2), None);
+        assert_eq!(app.board_position(5, 9), None);
+
+        send(&mut app, MouseEventKind::Down(MouseButton::Left), 5, 3);
+        assert_eq!(app.board.get_task(app.pressed_task.unwrap()).unwrap().title, "e");
+        assert_eq!((app.selected_column, app.selected_task), (1, 4));
+    }
+
+    #[test]
+    fn dropping_on_a_card_takes_its_slot_and_below_the_last_appends() {
+        let mut app = paged_app(&["a", "b"], &["x", "y"]);
+
+        drag(&mut app, (5, 2), (35, 3));
+        assert_eq!(titles(&app, 1), ["b"]);
+        assert_eq!(titles(&app, 2), ["x", "a", "y"]);
+        assert!(matches!(app.input_mode, InputMode::Normal));
+
+        drag(&mut app, (5, 2), (35, 6));
+        assert_eq!(titles(&app, 1), Vec::<&str>::new());
+        assert_eq!(titles(&app, 2), ["x", "a", "y", "b"]);
+        assert_eq!(app.status_message, "Task moved to Done");
+    }
+
+    #[test]
+    fn dropping_within_the_same_column_reorders() {
+        let mut app = paged_app(&["a", "b", "c", "d"], &[]);
+
+        drag(&mut app, (5, 2), (5, 4));
+        assert_eq!(titles(&app, 1), ["b", "c", "a", "d"]);
+        assert_eq!(app.status_message, "Task reordered in In Progress");
+
+        drag(&mut app, (5, 2), (5, 7));
+        assert_eq!(titles(&app, 1), ["c", "a", "d", "b"]);
+
+        // Dropping a card back onto itself is not a move
+        drag(&mut app, (5, 5), (5, 5));
+        assert_eq!(titles(&app, 1), ["c", "a", "d", "b"]);
+        assert_eq!(app.status_message, "Task not moved");
+
+        app.undo();
+        app.undo();
+        assert_eq!(titles(&app, 1), ["a", "b", "c", "d"]);
+    }
+}
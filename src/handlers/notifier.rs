@@ -0,0 +1,51 @@
+use std::{
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Delivers due date reminders raised by `App::tick`.
+pub trait Notifier {
+    fn notify(&mut self, summary: &str, body: &str);
+}
+
+/// Drops every reminder. Used when desktop notifications are disabled and
+/// as a stand-in wherever reminders should have no side effects.
+pub struct NoopNotifier;
+
+impl Notifier for NoopNotifier {
+    fn notify(&mut self, _summary: &str, _body: &str) {}
+}
+
+/// Shows a desktop notification through `notify-send` (Linux) or
+/// `osascript` (macOS). Failures are ignored: a missing notification daemon
+/// must never interrupt the board.
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&mut self, summary: &str, body: &str) {
+        let mut command = if cfg!(target_os = "macos") {
+            let script = format!(
+                "display notification {:?} with title {:?}",
+                body, summary
+            );
+            let mut command = Command::new("osascript");
+            command.arg("-e").arg(script);
+            command
+        } else {
+            let mut command = Command::new("notify-send");
+            command.arg("--app-name=kanban").arg(summary).arg(body);
+            command
+        };
+
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        // Wait on a background thread so the child is reaped without
+        // blocking the UI.
+        thread::spawn(move || {
+            let _ = command.status();
+        });
+    }
+}
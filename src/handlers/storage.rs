@@ -1,217 +1,523 @@
-use std::io;
-use rusqlite::{Connection, Result as SqlResult, params};
+use std::{
+    collections::HashMap,
+    env,
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+use rusqlite::{Connection, Result as SqlResult, Row, params};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use crate::models::{Board, column::Column, task::{Task, Priority}};
+use super::migrations::{self, MigrationError};
+use crate::models::{
+    Board,
+    board::HiddenTask,
+    changes::ChangeSet,
+    column::{Column, WipPolicy},
+    event::{EventKind, TaskEvent},
+    filter::SavedView,
+    label::{Label, LabelColor},
+    task::{ChecklistItem, Task, Priority},
+};
 
-const DB_FILE: &str = "kanban_board.db";
+/// Per-project database looked up from the working directory upwards.
+const PROJECT_DB_FILE: &str = ".kanban.db";
+const DB_FILE: &str = "kanban.db";
 
-pub fn init_database() -> SqlResult<Connection> {
-    let conn = Connection::open(DB_FILE)?;
-    
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS boards (
-            id TEXT PRIMARY KEY,
-            title TEXT NOT NULL
-        )",
-        [],
-    )?;
+static DB_PATH: OnceLock<PathBuf> = OnceLock::new();
 
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS columns (
-            id TEXT PRIMARY KEY,
-            title TEXT NOT NULL,
-            board_id TEXT NOT NULL,
-            position INTEGER NOT NULL,
-            FOREIGN KEY(board_id) REFERENCES boards(id)
-        )",
-        [],
-    )?;
+/// Lightweight listing entry used by the board picker, so switching boards
+/// doesn't require loading every column and task up front.
+#[derive(Clone, Debug)]
+pub struct BoardSummary {
+    pub id: Uuid,
+    pub title: String,
+    pub task_count: usize,
+}
 
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS tasks (
-            id TEXT PRIMARY KEY,
-            title TEXT NOT NULL,
-            description TEXT,
-            due_date TEXT,
-            priority TEXT NOT NULL,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL,
-            column_id TEXT NOT NULL,
-            position INTEGER NOT NULL,
-            FOREIGN KEY(column_id) REFERENCES columns(id)
-        )",
-        [],
-    )?;
+fn db_error(e: rusqlite::Error) -> io::Error {
+    io::Error::other(format!("Database error: {}", e))
+}
+
+fn parse_uuid(value: &str) -> io::Result<Uuid> {
+    Uuid::parse_str(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid UUID: {}", e)))
+}
+
+fn parse_date(value: &str) -> io::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid date: {}", e)))
+}
+
+/// Picks the database file, in order of precedence: an explicit `--db` path,
+/// the `KANBAN_DB` environment variable, a `.kanban.db` in the working
+/// directory or any of its parents, and finally the user data directory
+/// (`$XDG_DATA_HOME/kanban/kanban.db`, or `~/.local/share/kanban/kanban.db`).
+pub fn resolve_database_path(cli_path: Option<PathBuf>) -> io::Result<PathBuf> {
+    if let Some(path) = cli_path {
+        return Ok(path);
+    }
+
+    let path = resolve_path(None, |name| env::var_os(name), &env::current_dir()?)?;
+    if let Some(data_dir) = data_dir(|name| env::var_os(name)).filter(|dir| path.parent() == Some(dir)) {
+        fs::create_dir_all(data_dir)?;
+    }
+    Ok(path)
+}
+
+/// The precedence rules of `resolve_database_path`, with the environment and
+/// working directory passed in. Creates nothing on disk.
+fn resolve_path(
+    cli_path: Option<PathBuf>,
+    env: impl Fn(&str) -> Option<OsString>,
+    cwd: &Path,
+) -> io::Result<PathBuf> {
+    if let Some(path) = cli_path {
+        return Ok(path);
+    }
+
+    if let Some(path) = env("KANBAN_DB").filter(|value| !value.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+
+    if let Some(path) = find_project_database(cwd) {
+        return Ok(path);
+    }
+
+    let data_dir = data_dir(&env).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "Cannot determine data directory: set XDG_DATA_HOME or HOME, or pass --db",
+        )
+    })?;
+    Ok(data_dir.join(DB_FILE))
+}
+
+fn find_project_database(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_DB_FILE))
+        .find(|candidate| candidate.is_file())
+}
+
+fn data_dir(env: impl Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    let base = env("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(base.join("kanban"))
+}
+
+/// Sets the database file used by every storage call. Must be called before
+/// the database is first opened; later calls are ignored.
+pub fn set_database_path(path: PathBuf) {
+    let _ = DB_PATH.set(path);
+}
+
+pub fn database_path() -> &'static Path {
+    DB_PATH.get_or_init(|| PathBuf::from(DB_FILE))
+}
+
+/// Opens the database and applies any pending schema migrations. Fails
+/// with `InvalidData` if the file was written by a newer schema version.
+pub fn init_database() -> io::Result<Connection> {
+    let mut conn = Connection::open(database_path()).map_err(db_error)?;
+    conn.pragma_update(None, "foreign_keys", true).map_err(db_error)?;
+
+    migrations::migrate(&mut conn).map_err(|e| match e {
+        MigrationError::Sql(e) => db_error(e),
+        e => io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+    })?;
 
     Ok(conn)
 }
 
-pub fn save_board(board: &Board) -> io::Result<()> {
-    let conn = init_database()
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+/// Lists every board in the database in creation order.
+pub fn list_boards() -> io::Result<Vec<BoardSummary>> {
+    read_board_list(&init_database()?)
+}
+
+fn read_board_list(conn: &Connection) -> io::Result<Vec<BoardSummary>> {
+    let mut stmt = conn.prepare(
+        "SELECT b.id, b.title,
+                (SELECT COUNT(*) FROM tasks t
+                 JOIN columns c ON t.column_id = c.id
+                 WHERE c.board_id = b.id
+                   AND t.archived_at IS NULL AND t.deleted_at IS NULL)
+         FROM boards b ORDER BY b.rowid"
+    ).map_err(db_error)?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    }).map_err(db_error)?;
+
+    let mut boards = Vec::new();
+    for row in rows {
+        let (id, title, task_count) = row.map_err(db_error)?;
+        boards.push(BoardSummary {
+            id: parse_uuid(&id)?,
+            title,
+            task_count: task_count as usize,
+        });
+    }
+
+    Ok(boards)
+}
 
-    conn.execute("DELETE FROM tasks", [])
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
-    conn.execute("DELETE FROM columns", [])
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
-    conn.execute("DELETE FROM boards", [])
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+fn priority_to_str(priority: &Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Medium => "Medium",
+        Priority::High => "High",
+        Priority::Critical => "Critical",
+    }
+}
 
+fn upsert_task(conn: &Connection, task: &Task, column_id: Uuid, position: usize) -> SqlResult<()> {
     conn.execute(
-        "INSERT INTO boards (id, title) VALUES (?1, ?2)",
-        params![board.id.to_string(), board.title],
-    ).map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+        "INSERT INTO tasks (id, title, description, due_date, priority, created_at, updated_at, column_id, position,
+                            archived_at, deleted_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+         ON CONFLICT(id) DO UPDATE SET
+            title = excluded.title,
+            description = excluded.description,
+            due_date = excluded.due_date,
+            priority = excluded.priority,
+            updated_at = excluded.updated_at,
+            column_id = excluded.column_id,
+            position = excluded.position,
+            archived_at = excluded.archived_at,
+            deleted_at = excluded.deleted_at",
+        params![
+            task.id.to_string(),
+            task.title,
+            task.description,
+            task.due_date.map(|d| d.to_rfc3339()),
+            priority_to_str(&task.priority),
+            task.created_at.to_rfc3339(),
+            task.updated_at.to_rfc3339(),
+            column_id.to_string(),
+            position as i32,
+            task.archived_at.map(|d| d.to_rfc3339()),
+            task.deleted_at.map(|d| d.to_rfc3339()),
+        ],
+    )?;
 
-    for (col_pos, column) in board.columns.iter().enumerate() {
+    conn.execute("DELETE FROM task_labels WHERE task_id = ?1", params![task.id.to_string()])?;
+    for label_id in &task.labels {
         conn.execute(
-            "INSERT INTO columns (id, title, board_id, position) VALUES (?1, ?2, ?3, ?4)",
-            params![column.id.to_string(), column.title, board.id.to_string(), col_pos as i32],
-        ).map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+            "INSERT INTO task_labels (task_id, label_id) VALUES (?1, ?2)",
+            params![task.id.to_string(), label_id.to_string()],
+        )?;
+    }
 
-        for (task_pos, task) in column.tasks.iter().enumerate() {
-            let priority_str = match task.priority {
-                Priority::Low => "Low",
-                Priority::Medium => "Medium", 
-                Priority::High => "High",
-                Priority::Critical => "Critical",
-            };
+    conn.execute("DELETE FROM checklist_items WHERE task_id = ?1", params![task.id.to_string()])?;
+    for (item_pos, item) in task.checklist.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO checklist_items (id, task_id, text, done, position) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![item.id.to_string(), task.id.to_string(), item.text, item.done, item_pos as i32],
+        )?;
+    }
+
+    conn.execute("DELETE FROM task_dependencies WHERE task_id = ?1", params![task.id.to_string()])?;
+    for blocker_id in &task.blocked_by {
+        conn.execute(
+            "INSERT INTO task_dependencies (task_id, blocker_id) VALUES (?1, ?2)",
+            params![task.id.to_string(), blocker_id.to_string()],
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the rows recorded in `board.changes` inside a single transaction.
+/// On failure nothing is committed and the change set is kept, so the
+/// database still holds the previous state and the next save retries.
+pub fn save_board(board: &mut Board) -> io::Result<()> {
+    if !board.is_dirty() {
+        return Ok(());
+    }
 
+    let mut conn = init_database()?;
+    save_changes(&mut conn, board)
+}
+
+fn save_changes(conn: &mut Connection, board: &mut Board) -> io::Result<()> {
+    let tx = conn.transaction().map_err(db_error)?;
+    write_changes(&tx, board).map_err(db_error)?;
+    tx.commit().map_err(db_error)?;
+
+    board.changes.clear();
+    board.events.clear();
+    Ok(())
+}
+
+fn write_changes(conn: &Connection, board: &Board) -> SqlResult<()> {
+    let changes = &board.changes;
+    let board_id = board.id.to_string();
+
+    if changes.full || changes.board {
+        conn.execute(
+            "INSERT INTO boards (id, title) VALUES (?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET title = excluded.title",
+            params![board_id, board.title],
+        )?;
+    }
+
+    // Labels first so tasks written below can reference new ones.
+    for (label_pos, label) in board.labels.iter().enumerate() {
+        if changes.full || changes.labels.contains(&label.id) {
+            conn.execute(
+                "INSERT INTO labels (id, board_id, name, color, position) VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    color = excluded.color,
+                    position = excluded.position",
+                params![label.id.to_string(), board_id, label.name, label.color.as_str(), label_pos as i32],
+            )?;
+        }
+    }
+
+    for (view_pos, view) in board.views.iter().enumerate() {
+        if changes.full || changes.views.contains(&view.id) {
+            let filter = serde_json::to_string(&view.filter)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
             conn.execute(
-                "INSERT INTO tasks (id, title, description, due_date, priority, created_at, updated_at, column_id, position) 
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                "INSERT INTO views (id, board_id, name, filter, position) VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    filter = excluded.filter,
+                    position = excluded.position",
+                params![view.id.to_string(), board_id, view.name, filter, view_pos as i32],
+            )?;
+        }
+    }
+
+    // Links from tasks stored as archived or trashed go with the label.
+    for label_id in &changes.deleted_labels {
+        conn.execute("DELETE FROM labels WHERE id = ?1", params![label_id.to_string()])?;
+    }
+
+    for view_id in &changes.deleted_views {
+        conn.execute("DELETE FROM views WHERE id = ?1", params![view_id.to_string()])?;
+    }
+
+    for task_id in &changes.deleted_tasks {
+        conn.execute("DELETE FROM task_dependencies WHERE blocker_id = ?1", params![task_id.to_string()])?;
+        conn.execute("DELETE FROM tasks WHERE id = ?1", params![task_id.to_string()])?;
+    }
+
+    for (col_pos, column) in board.columns.iter().enumerate() {
+        let column_dirty = changes.full || changes.columns.contains(&column.id);
+
+        if column_dirty || changes.column_order {
+            conn.execute(
+                "INSERT INTO columns (id, title, board_id, position, wip_limit, wip_policy)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(id) DO UPDATE SET
+                    title = excluded.title,
+                    board_id = excluded.board_id,
+                    position = excluded.position,
+                    wip_limit = excluded.wip_limit,
+                    wip_policy = excluded.wip_policy",
                 params![
-                    task.id.to_string(),
-                    task.title,
-                    task.description,
-                    task.due_date.map(|d| d.to_rfc3339()),
-                    priority_str,
-                    task.created_at.to_rfc3339(),
-                    task.updated_at.to_rfc3339(),
                     column.id.to_string(),
-                    task_pos as i32
+                    column.title,
+                    board_id,
+                    col_pos as i32,
+                    column.wip_limit.map(|limit| limit as i64),
+                    column.wip_policy.as_str(),
                 ],
-            ).map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+            )?;
+        }
+
+        for (task_pos, task) in column.tasks.iter().enumerate() {
+            if column_dirty || changes.tasks.contains(&task.id) {
+                upsert_task(conn, task, column.id, task_pos)?;
+            }
         }
     }
 
+    for hidden in &board.hidden {
+        if changes.full || changes.tasks.contains(&hidden.task.id) {
+            upsert_task(conn, &hidden.task, hidden.column_id, 0)?;
+        }
+    }
+
+    // Runs last: the visible tasks of a deleted column were either moved or
+    // trashed and upserted above. Archived and trashed tasks that still point
+    // at it are filed under the first remaining column instead.
+    for column_id in &changes.deleted_columns {
+        if let Some(fallback) = board.columns.first() {
+            conn.execute(
+                "UPDATE tasks SET column_id = ?1 WHERE column_id = ?2",
+                params![fallback.id.to_string(), column_id.to_string()],
+            )?;
+        }
+        conn.execute("DELETE FROM columns WHERE id = ?1", params![column_id.to_string()])?;
+    }
+
+    // Before the new events, which may belong to a task re-added by redo
+    for task_id in &changes.deleted_events {
+        conn.execute("DELETE FROM task_events WHERE task_id = ?1", params![task_id.to_string()])?;
+    }
+    for event in &board.events {
+        insert_event(conn, &board_id, event)?;
+    }
+
     Ok(())
 }
 
-pub fn load_board() -> io::Result<Board> {
-    let conn = init_database()
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+fn insert_event(conn: &Connection, board_id: &str, event: &TaskEvent) -> SqlResult<()> {
+    let (field, old_value, new_value) = match &event.kind {
+        EventKind::Changed { field, old, new } => (Some(field.clone()), Some(old.clone()), Some(new.clone())),
+        EventKind::Moved { from, to } => (None, Some(from.to_string()), Some(to.to_string())),
+        _ => (None, None, None),
+    };
 
-    let mut stmt = conn.prepare("SELECT id, title FROM boards LIMIT 1")
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
-    
-    let board_row = stmt.query_row([], |row| {
-        Ok((
-            row.get::<_, String>(0)?,
-            row.get::<_, String>(1)?,
-        ))
-    });
-
-    let (board_id, board_title) = match board_row {
-        Ok((id, title)) => (id, title),
-        Err(_) => {
-            let new_board = Board::new("My Kanban Board".to_string());
-            save_board(&new_board)?;
-            return Ok(new_board);
+    conn.execute(
+        "INSERT INTO task_events (board_id, task_id, at, kind, field, old_value, new_value)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            board_id,
+            event.task_id.to_string(),
+            event.at.to_rfc3339(),
+            event.kind.as_str(),
+            field,
+            old_value,
+            new_value,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Renames a board without loading it.
+pub fn rename_board(board_id: Uuid, title: &str) -> io::Result<()> {
+    let conn = init_database()?;
+    conn.execute(
+        "UPDATE boards SET title = ?1 WHERE id = ?2",
+        params![title, board_id.to_string()],
+    ).map_err(db_error)?;
+    Ok(())
+}
+
+/// Removes a board together with all of its columns and tasks.
+pub fn delete_board(board_id: Uuid) -> io::Result<()> {
+    remove_board(&mut init_database()?, board_id)
+}
+
+fn remove_board(conn: &mut Connection, board_id: Uuid) -> io::Result<()> {
+    let tx = conn.transaction().map_err(db_error)?;
+    let board_id = board_id.to_string();
+
+    tx.execute(
+        "DELETE FROM tasks WHERE column_id IN (SELECT id FROM columns WHERE board_id = ?1)",
+        params![board_id],
+    ).map_err(db_error)?;
+    tx.execute("DELETE FROM labels WHERE board_id = ?1", params![board_id])
+        .map_err(db_error)?;
+    tx.execute("DELETE FROM views WHERE board_id = ?1", params![board_id])
+        .map_err(db_error)?;
+    tx.execute("DELETE FROM task_events WHERE board_id = ?1", params![board_id])
+        .map_err(db_error)?;
+    tx.execute("DELETE FROM columns WHERE board_id = ?1", params![board_id])
+        .map_err(db_error)?;
+    tx.execute("DELETE FROM boards WHERE id = ?1", params![board_id])
+        .map_err(db_error)?;
+
+    tx.commit().map_err(db_error)
+}
+
+/// Loads the first board in the database, creating and saving a fresh one
+/// if the database is empty.
+pub fn load_first_board() -> io::Result<Board> {
+    match list_boards()?.first() {
+        Some(summary) => load_board(summary.id),
+        None => {
+            let mut new_board = Board::new("My Kanban Board".to_string());
+            save_board(&mut new_board)?;
+            Ok(new_board)
         }
-    };
+    }
+}
+
+pub fn load_board(board_id: Uuid) -> io::Result<Board> {
+    read_board(&init_database()?, board_id)
+}
+
+fn read_board(conn: &Connection, board_id: Uuid) -> io::Result<Board> {
+    let board_id = board_id.to_string();
 
-    let board_uuid = Uuid::parse_str(&board_id)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid UUID: {}", e)))?;
+    let board_title = conn.query_row(
+        "SELECT title FROM boards WHERE id = ?1",
+        [&board_id],
+        |row| row.get::<_, String>(0),
+    ).map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            io::Error::new(io::ErrorKind::NotFound, format!("Board {} not found", board_id))
+        }
+        e => db_error(e),
+    })?;
+
+    let board_uuid = parse_uuid(&board_id)?;
+
+    let labels = load_labels(conn, &board_id)?;
+    let views = load_views(conn, &board_id)?;
+    let mut task_labels = load_task_labels(conn, &board_id)?;
+    let mut checklists = load_checklists(conn, &board_id)?;
+    let mut blockers = load_blockers(conn, &board_id)?;
 
     let mut columns_stmt = conn.prepare(
-        "SELECT id, title FROM columns WHERE board_id = ?1 ORDER BY position"
-    ).map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+        "SELECT id, title, wip_limit, wip_policy FROM columns WHERE board_id = ?1 ORDER BY position"
+    ).map_err(db_error)?;
 
     let column_rows = columns_stmt.query_map([&board_id], |row| {
         Ok((
             row.get::<_, String>(0)?,
             row.get::<_, String>(1)?,
+            row.get::<_, Option<i64>>(2)?,
+            row.get::<_, String>(3)?,
         ))
-    }).map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+    }).map_err(db_error)?;
 
     let mut columns = Vec::new();
 
     for column_row in column_rows {
-        let (column_id, column_title) = column_row
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
-        
-        let column_uuid = Uuid::parse_str(&column_id)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid UUID: {}", e)))?;
-
-        let mut tasks_stmt = conn.prepare(
-            "SELECT id, title, description, due_date, priority, created_at, updated_at 
-             FROM tasks WHERE column_id = ?1 ORDER BY position"
-        ).map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
-
-        let task_rows = tasks_stmt.query_map([&column_id], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, Option<String>>(2)?,
-                row.get::<_, Option<String>>(3)?,
-                row.get::<_, String>(4)?,
-                row.get::<_, String>(5)?,
-                row.get::<_, String>(6)?,
-            ))
-        }).map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
+        let (column_id, column_title, wip_limit, wip_policy) = column_row.map_err(db_error)?;
 
-        let mut tasks = Vec::new();
+        let column_uuid = parse_uuid(&column_id)?;
 
+        let mut tasks_stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             WHERE t.column_id = ?1 AND t.archived_at IS NULL AND t.deleted_at IS NULL
+             ORDER BY t.position",
+            TASK_FIELDS
+        )).map_err(db_error)?;
+
+        let task_rows = tasks_stmt.query_map([&column_id], read_task_row).map_err(db_error)?;
+
+        let mut tasks = Vec::new();
         for task_row in task_rows {
-            let (task_id, title, description, due_date, priority_str, created_at, updated_at) = task_row
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Database error: {}", e)))?;
-
-            let task_uuid = Uuid::parse_str(&task_id)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid UUID: {}", e)))?;
-
-            let priority = match priority_str.as_str() {
-                "Low" => Priority::Low,
-                "Medium" => Priority::Medium,
-                "High" => Priority::High,
-                "Critical" => Priority::Critical,
-                _ => Priority::Medium,
-            };
-
-            let due_date_parsed = if let Some(due_str) = due_date {
-                Some(DateTime::parse_from_rfc3339(&due_str)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid date: {}", e)))?
-                    .with_timezone(&Utc))
-            } else {
-                None
-            };
-
-            let created_at_parsed = DateTime::parse_from_rfc3339(&created_at)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid date: {}", e)))?
-                .with_timezone(&Utc);
-
-            let updated_at_parsed = DateTime::parse_from_rfc3339(&updated_at)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid date: {}", e)))?
-                .with_timezone(&Utc);
-
-            let task = Task {
-                id: task_uuid,
-                title,
-                description,
-                due_date: due_date_parsed,
-                priority,
-                created_at: created_at_parsed,
-                updated_at: updated_at_parsed,
-            };
-
-            tasks.push(task);
+            tasks.push(build_task(
+                task_row.map_err(db_error)?,
+                &mut task_labels,
+                &mut checklists,
+                &mut blockers,
+            )?);
         }
 
         let column = Column {
             id: column_uuid,
             title: column_title,
             tasks,
+            wip_limit: wip_limit.map(|limit| limit as usize),
+            wip_policy: WipPolicy::parse(&wip_policy).unwrap_or_default(),
         };
 
         columns.push(column);
@@ -221,6 +527,500 @@ pub fn load_board() -> io::Result<Board> {
         id: board_uuid,
         title: board_title,
         columns,
+        labels,
+        views,
+        hidden: Vec::new(),
+        events: Vec::new(),
+        changes: ChangeSet::default(),
     })
 }
 
+/// Activity log of a task, oldest first.
+pub fn load_task_events(task_id: Uuid) -> io::Result<Vec<TaskEvent>> {
+    let conn = init_database()?;
+    query_events(&conn, "task_id", &task_id.to_string())
+}
+
+/// Activity of every task on a board, oldest first.
+pub fn load_board_events(board_id: Uuid) -> io::Result<Vec<TaskEvent>> {
+    let conn = init_database()?;
+    query_events(&conn, "board_id", &board_id.to_string())
+}
+
+/// Events whose `key_column` equals `value`; `key_column` is never user input.
+fn query_events(conn: &Connection, key_column: &str, value: &str) -> io::Result<Vec<TaskEvent>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT task_id, at, kind, field, old_value, new_value FROM task_events
+         WHERE {} = ?1 ORDER BY id",
+        key_column
+    )).map_err(db_error)?;
+
+    let rows = stmt.query_map([value], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, Option<String>>(5)?,
+        ))
+    }).map_err(db_error)?;
+
+    let mut events = Vec::new();
+    for row in rows {
+        let (task_id, at, kind, field, old_value, new_value) = row.map_err(db_error)?;
+        let kind = match kind.as_str() {
+            "created" => EventKind::Created,
+            "changed" => EventKind::Changed {
+                field: field.unwrap_or_default(),
+                old: old_value.unwrap_or_default(),
+                new: new_value.unwrap_or_default(),
+            },
+            "moved" => EventKind::Moved {
+                from: parse_uuid(old_value.as_deref().unwrap_or_default())?,
+                to: parse_uuid(new_value.as_deref().unwrap_or_default())?,
+            },
+            "archived" => EventKind::Archived,
+            "deleted" => EventKind::Deleted,
+            "restored" => EventKind::Restored,
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unknown task event: {}", other),
+                ));
+            }
+        };
+        events.push(TaskEvent {
+            task_id: parse_uuid(&task_id)?,
+            at: parse_date(&at)?,
+            kind,
+        });
+    }
+    Ok(events)
+}
+
+/// Archived and trashed tasks of a board, most recently hidden first.
+pub fn load_hidden_tasks(board_id: Uuid) -> io::Result<Vec<HiddenTask>> {
+    let conn = init_database()?;
+    let board_id = board_id.to_string();
+    let mut task_labels = load_task_labels(&conn, &board_id)?;
+    let mut checklists = load_checklists(&conn, &board_id)?;
+    let mut blockers = load_blockers(&conn, &board_id)?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, t.column_id FROM tasks t
+         JOIN columns c ON t.column_id = c.id
+         WHERE c.board_id = ?1 AND (t.archived_at IS NOT NULL OR t.deleted_at IS NOT NULL)
+         ORDER BY COALESCE(t.deleted_at, t.archived_at) DESC",
+        TASK_FIELDS
+    )).map_err(db_error)?;
+
+    let rows = stmt.query_map([&board_id], |row| {
+        Ok((read_task_row(row)?, row.get::<_, String>(TASK_FIELD_COUNT)?))
+    }).map_err(db_error)?;
+
+    let mut hidden = Vec::new();
+    for row in rows {
+        let (task_row, column_id) = row.map_err(db_error)?;
+        hidden.push(HiddenTask {
+            column_id: parse_uuid(&column_id)?,
+            task: build_task(task_row, &mut task_labels, &mut checklists, &mut blockers)?,
+        });
+    }
+    Ok(hidden)
+}
+
+/// Columns selected by `read_task_row`, in order.
+const TASK_FIELDS: &str =
+    "t.id, t.title, t.description, t.due_date, t.priority, t.created_at, t.updated_at, t.archived_at, t.deleted_at";
+const TASK_FIELD_COUNT: usize = 9;
+
+struct TaskRow {
+    id: String,
+    title: String,
+    description: Option<String>,
+    due_date: Option<String>,
+    priority: String,
+    created_at: String,
+    updated_at: String,
+    archived_at: Option<String>,
+    deleted_at: Option<String>,
+}
+
+fn read_task_row(row: &Row) -> SqlResult<TaskRow> {
+    Ok(TaskRow {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        description: row.get(2)?,
+        due_date: row.get(3)?,
+        priority: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+        archived_at: row.get(7)?,
+        deleted_at: row.get(8)?,
+    })
+}
+
+fn build_task(
+    row: TaskRow,
+    task_labels: &mut HashMap<String, Vec<Uuid>>,
+    checklists: &mut HashMap<String, Vec<ChecklistItem>>,
+    blockers: &mut HashMap<String, Vec<Uuid>>,
+) -> io::Result<Task> {
+    let priority = match row.priority.as_str() {
+        "Low" => Priority::Low,
+        "Medium" => Priority::Medium,
+        "High" => Priority::High,
+        "Critical" => Priority::Critical,
+        _ => Priority::Medium,
+    };
+
+    Ok(Task {
+        id: parse_uuid(&row.id)?,
+        title: row.title,
+        description: row.description,
+        due_date: row.due_date.as_deref().map(parse_date).transpose()?,
+        priority,
+        labels: task_labels.remove(&row.id).unwrap_or_default(),
+        checklist: checklists.remove(&row.id).unwrap_or_default(),
+        blocked_by: blockers.remove(&row.id).unwrap_or_default(),
+        created_at: parse_date(&row.created_at)?,
+        updated_at: parse_date(&row.updated_at)?,
+        archived_at: row.archived_at.as_deref().map(parse_date).transpose()?,
+        deleted_at: row.deleted_at.as_deref().map(parse_date).transpose()?,
+    })
+}
+
+fn load_views(conn: &Connection, board_id: &str) -> io::Result<Vec<SavedView>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, filter FROM views WHERE board_id = ?1 ORDER BY position"
+    ).map_err(db_error)?;
+
+    let rows = stmt.query_map([board_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    }).map_err(db_error)?;
+
+    let mut views = Vec::new();
+    for row in rows {
+        let (id, name, filter) = row.map_err(db_error)?;
+        let filter = serde_json::from_str(&filter)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid view filter: {}", e)))?;
+        views.push(SavedView {
+            id: parse_uuid(&id)?,
+            name,
+            filter,
+        });
+    }
+    Ok(views)
+}
+
+fn load_labels(conn: &Connection, board_id: &str) -> io::Result<Vec<Label>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, color FROM labels WHERE board_id = ?1 ORDER BY position"
+    ).map_err(db_error)?;
+
+    let rows = stmt.query_map([board_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    }).map_err(db_error)?;
+
+    let mut labels = Vec::new();
+    for row in rows {
+        let (id, name, color) = row.map_err(db_error)?;
+        labels.push(Label {
+            id: parse_uuid(&id)?,
+            name,
+            color: LabelColor::parse(&color).unwrap_or(LabelColor::Gray),
+        });
+    }
+    Ok(labels)
+}
+
+/// Checklist items of every task on the board, keyed by the task's id string.
+fn load_checklists(conn: &Connection, board_id: &str) -> io::Result<HashMap<String, Vec<ChecklistItem>>> {
+    let mut stmt = conn.prepare(
+        "SELECT ci.task_id, ci.id, ci.text, ci.done FROM checklist_items ci
+         JOIN tasks t ON ci.task_id = t.id
+         JOIN columns c ON t.column_id = c.id
+         WHERE c.board_id = ?1
+         ORDER BY ci.position"
+    ).map_err(db_error)?;
+
+    let rows = stmt.query_map([board_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, bool>(3)?,
+        ))
+    }).map_err(db_error)?;
+
+    let mut checklists: HashMap<String, Vec<ChecklistItem>> = HashMap::new();
+    for row in rows {
+        let (task_id, id, text, done) = row.map_err(db_error)?;
+        checklists.entry(task_id).or_default().push(ChecklistItem {
+            id: parse_uuid(&id)?,
+            text,
+            done,
+        });
+    }
+    Ok(checklists)
+}
+
+/// Ids of the tasks every task on the board waits for, keyed by the task's
+/// id string. Links to tasks that no longer exist are skipped.
+fn load_blockers(conn: &Connection, board_id: &str) -> io::Result<HashMap<String, Vec<Uuid>>> {
+    let mut stmt = conn.prepare(
+        "SELECT d.task_id, d.blocker_id FROM task_dependencies d
+         JOIN tasks t ON d.task_id = t.id
+         JOIN tasks b ON d.blocker_id = b.id
+         JOIN columns c ON t.column_id = c.id
+         WHERE c.board_id = ?1"
+    ).map_err(db_error)?;
+
+    let rows = stmt.query_map([board_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    }).map_err(db_error)?;
+
+    let mut blockers: HashMap<String, Vec<Uuid>> = HashMap::new();
+    for row in rows {
+        let (task_id, blocker_id) = row.map_err(db_error)?;
+        blockers.entry(task_id).or_default().push(parse_uuid(&blocker_id)?);
+    }
+    Ok(blockers)
+}
+
+/// Label ids of every task on the board, keyed by the task's id string.
+fn load_task_labels(conn: &Connection, board_id: &str) -> io::Result<HashMap<String, Vec<Uuid>>> {
+    let mut stmt = conn.prepare(
+        "SELECT tl.task_id, tl.label_id FROM task_labels tl
+         JOIN labels l ON tl.label_id = l.id
+         WHERE l.board_id = ?1
+         ORDER BY l.position"
+    ).map_err(db_error)?;
+
+    let rows = stmt.query_map([board_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    }).map_err(db_error)?;
+
+    let mut task_labels: HashMap<String, Vec<Uuid>> = HashMap::new();
+    for row in rows {
+        let (task_id, label_id) = row.map_err(db_error)?;
+        task_labels.entry(task_id).or_default().push(parse_uuid(&label_id)?);
+    }
+    Ok(task_labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_database() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        migrations::migrate(&mut conn).unwrap();
+        conn
+    }
+
+    fn count(conn: &Connection, table: &str) -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn counts(conn: &Connection) -> [i64; 4] {
+        ["boards", "columns", "tasks", "task_events"].map(|table| count(conn, table))
+    }
+
+    #[test]
+    fn failed_save_rolls_back_and_keeps_the_changes_for_a_retry() {
+        let mut conn = memory_database();
+        let mut board = Board::new("Test".to_string());
+        let column_id = board.columns[0].id;
+        board.create_task(column_id, 0, Task::new("Write tests".to_string()));
+
+        // The event log is written last, so everything before it must be undone
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER fail_events BEFORE INSERT ON task_events
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+        )
+        .unwrap();
+        assert!(save_changes(&mut conn, &mut board).is_err());
+        assert_eq!(counts(&conn), [0, 0, 0, 0]);
+        assert!(board.changes.full);
+        assert_eq!(board.events.len(), 1);
+
+        conn.execute_batch("DROP TRIGGER fail_events").unwrap();
+        save_changes(&mut conn, &mut board).unwrap();
+        assert_eq!(counts(&conn), [1, 3, 1, 1]);
+        assert!(!board.is_dirty());
+        assert!(board.events.is_empty());
+    }
+
+    #[test]
+    fn incremental_saves_write_only_what_changed() {
+        let mut conn = memory_database();
+        let mut board = Board::new("Test".to_string());
+        save_changes(&mut conn, &mut board).unwrap();
+
+        let (from, to) = (board.columns[0].id, board.columns[1].id);
+        let task = Task::new("Move me".to_string());
+        let task_id = task.id;
+        board.create_task(from, 0, task);
+        board.move_task(task_id, to, 0);
+        save_changes(&mut conn, &mut board).unwrap();
+
+        let stored_column: String = conn
+            .query_row("SELECT column_id FROM tasks WHERE id = ?1", [task_id.to_string()], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored_column, to.to_string());
+        assert_eq!(counts(&conn), [1, 3, 1, 2]);
+    }
+
+    #[test]
+    fn only_unused_labels_are_deleted() {
+        let mut conn = memory_database();
+        let mut board = Board::new("Test".to_string());
+        let (used, unused) = (board.ensure_label("bug"), board.ensure_label("idea"));
+        let mut task = Task::new("Labelled".to_string());
+        task.labels.push(used);
+        board.create_task(board.columns[0].id, 0, task);
+        save_changes(&mut conn, &mut board).unwrap();
+
+        assert!(!board.delete_label(used));
+        assert!(board.delete_label(unused));
+        assert!(!board.delete_label(unused));
+        save_changes(&mut conn, &mut board).unwrap();
+
+        let names: Vec<String> = conn
+            .prepare("SELECT name FROM labels")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<SqlResult<_>>()
+            .unwrap();
+        assert_eq!(names, ["bug"]);
+        assert_eq!(count(&conn, "task_labels"), 1);
+    }
+
+    #[test]
+    fn label_names_and_colors_are_saved() {
+        let mut conn = memory_database();
+        let mut board = Board::new("Test".to_string());
+        let (bug, idea) = (board.ensure_label("bug"), board.ensure_label("idea"));
+        save_changes(&mut conn, &mut board).unwrap();
+
+        assert!(!board.rename_label(idea, "BUG"));
+        assert!(!board.rename_label(idea, "  "));
+        assert!(board.rename_label(idea, " feature "));
+        assert!(board.rename_label(bug, "Bug"));
+        let color = board.get_label(bug).unwrap().color.next();
+        assert!(board.set_label_color(bug, color));
+        save_changes(&mut conn, &mut board).unwrap();
+
+        let labels = load_labels(&conn, &board.id.to_string()).unwrap();
+        let stored: Vec<(&str, LabelColor)> = labels.iter().map(|label| (label.name.as_str(), label.color)).collect();
+        assert_eq!(stored, [("Bug", color), ("feature", LabelColor::PALETTE[1])]);
+    }
+
+    fn board_with_task(title: &str, task: &str) -> Board {
+        let mut board = Board::new(title.to_string());
+        let label_id = board.ensure_label("shared name");
+        let mut task = Task::new(task.to_string());
+        task.labels.push(label_id);
+        board.create_task(board.columns[1].id, 0, task);
+        board
+    }
+
+    fn task_titles(board: &Board) -> Vec<Vec<&str>> {
+        board
+            .columns
+            .iter()
+            .map(|column| column.tasks.iter().map(|task| task.title.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn boards_are_listed_loaded_and_deleted_independently() {
+        let mut conn = memory_database();
+        let mut work = board_with_task("Work", "Ship release");
+        let mut home = board_with_task("Home", "Fix bike");
+        save_changes(&mut conn, &mut work).unwrap();
+        save_changes(&mut conn, &mut home).unwrap();
+
+        let summaries: Vec<(Uuid, String, usize)> = read_board_list(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|summary| (summary.id, summary.title, summary.task_count))
+            .collect();
+        assert_eq!(summaries, [(work.id, "Work".to_string(), 1), (home.id, "Home".to_string(), 1)]);
+
+        for board in [&work, &home] {
+            let loaded = read_board(&conn, board.id).unwrap();
+            assert_eq!(loaded.title, board.title);
+            assert_eq!(task_titles(&loaded), task_titles(board));
+            assert_eq!(loaded.labels.len(), 1);
+            assert_eq!(loaded.columns[1].tasks[0].labels, [loaded.labels[0].id]);
+        }
+
+        remove_board(&mut conn, work.id).unwrap();
+        let remaining: Vec<Uuid> = read_board_list(&conn).unwrap().iter().map(|summary| summary.id).collect();
+        assert_eq!(remaining, [home.id]);
+        assert_eq!(read_board(&conn, work.id).unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        let loaded = read_board(&conn, home.id).unwrap();
+        assert_eq!(loaded.columns.len(), 3);
+        assert_eq!(task_titles(&loaded), [vec![], vec!["Fix bike"], vec![]]);
+        assert_eq!(counts(&conn), [1, 3, 1, 1]);
+        assert_eq!(count(&conn, "labels"), 1);
+    }
+
+    fn fake_env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| OsString::from(value))
+        }
+    }
+
+    #[test]
+    fn database_path_follows_the_documented_precedence() {
+        let root = env::temp_dir().join(format!("kanban-path-{}", Uuid::new_v4()));
+        let nested = root.join("src/handlers");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(PROJECT_DB_FILE), "").unwrap();
+        let outside = env::temp_dir().join(format!("kanban-path-{}", Uuid::new_v4()));
+        fs::create_dir_all(&outside).unwrap();
+
+        let all = [("KANBAN_DB", "/env/board.db"), ("XDG_DATA_HOME", "/xdg"), ("HOME", "/home/me")];
+        let resolve = |cli: Option<&str>, vars: &[(&str, &str)], cwd: &Path| {
+            resolve_path(cli.map(PathBuf::from), fake_env(vars), cwd).unwrap()
+        };
+
+        assert_eq!(resolve(Some("cli.db"), &all, &nested), PathBuf::from("cli.db"));
+        assert_eq!(resolve(None, &all, &nested), PathBuf::from("/env/board.db"));
+        assert_eq!(resolve(None, &[("KANBAN_DB", "")], &nested), root.join(PROJECT_DB_FILE));
+        assert_eq!(resolve(None, &all[1..], &nested), root.join(PROJECT_DB_FILE));
+        assert_eq!(resolve(None, &all[1..], &root), root.join(PROJECT_DB_FILE));
+        assert_eq!(resolve(None, &all[1..], &outside), PathBuf::from("/xdg/kanban").join(DB_FILE));
+        assert_eq!(
+            resolve(None, &[("XDG_DATA_HOME", "relative"), ("HOME", "/home/me")], &outside),
+            PathBuf::from("/home/me/.local/share/kanban").join(DB_FILE)
+        );
+        assert_eq!(
+            resolve(None, &all[2..], &outside),
+            PathBuf::from("/home/me/.local/share/kanban").join(DB_FILE)
+        );
+        let error = resolve_path(None, fake_env(&[]), &outside).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+}
@@ -1,11 +1,19 @@
 use std::{
+    env,
     error::Error,
     io,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     time::{Duration, Instant},
 };
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyCode,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -20,28 +28,132 @@ mod models;
 mod ui;
 
 use app::App;
-use handlers::{input::handle_key_events, storage};
+use handlers::{
+    external_editor,
+    input::{handle_key_events, handle_paste},
+    mouse::handle_mouse_events,
+    notifier::DesktopNotifier,
+    storage,
+};
+
+const USAGE: &str = "Usage: kanban [--db <path>] [--notify] [--no-move-suggestions]
+
+Options:
+  --db <path>              Use the given database file
+  --notify                 Send desktop notifications for cards due today or overdue
+  --no-move-suggestions    Don't offer to move a card when its checklist is completed
+  -h, --help               Show this help
+
+Without --db the database is taken from $KANBAN_DB, then the nearest
+.kanban.db in the current directory or its parents, and otherwise
+$XDG_DATA_HOME/kanban/kanban.db (~/.local/share/kanban/kanban.db).";
+
+#[derive(Default)]
+struct Args {
+    db_path: Option<PathBuf>,
+    notify: bool,
+    no_move_suggestions: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "--db" => {
+                let path = args.next().ok_or("--db requires a path")?;
+                parsed.db_path = Some(PathBuf::from(path));
+            }
+            "--notify" => {
+                parsed.notify = true;
+            }
+            "--no-move-suggestions" => {
+                parsed.no_move_suggestions = true;
+            }
+            _ => match arg.strip_prefix("--db=") {
+                Some(path) => parsed.db_path = Some(PathBuf::from(path)),
+                None => return Err(format!("Unknown argument: {}", arg)),
+            },
+        }
+    }
+
+    Ok(parsed)
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    match storage::resolve_database_path(args.db_path) {
+        Ok(path) => storage::set_database_path(path),
+        Err(e) => {
+            eprintln!("Failed to locate database: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Open (and migrate) the database before touching the terminal so schema
+    // errors, such as a file written by a newer version, are readable.
+    if let Err(e) = storage::init_database() {
+        eprintln!("Failed to open database {}: {}", storage::database_path().display(), e);
+        std::process::exit(1);
+    }
+
+    // SIGTERM/SIGHUP only raise a flag; the main loop notices it and shuts down
+    // through the normal path so the board is flushed before exit.
+    let terminate = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGTERM, Arc::clone(&terminate))?;
+    #[cfg(unix)]
+    signal_hook::flag::register(signal_hook::consts::SIGHUP, Arc::clone(&terminate))?;
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run it
-    let app = App::new();
-    let res = run_app(&mut terminal, app);
+    let mut app = App::new();
+    if args.notify {
+        app.notifier = Box::new(DesktopNotifier);
+    }
+    app.suggest_next_column = !args.no_move_suggestions;
+    let res = run_app(&mut terminal, &mut app, &terminate);
+
+    // Flush whatever autosave hasn't written yet before touching the
+    // terminal: if the loop bailed out because the tty went away, restoring
+    // it fails too and must not cost the unsaved changes.
+    let saved = app.save();
+
+    // Restore terminal. Each step is tried even if an earlier one failed.
+    let restored = [
+        disable_raw_mode(),
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        ),
+        terminal.show_cursor(),
+    ];
+    for e in restored.into_iter().filter_map(Result::err) {
+        eprintln!("Failed to restore terminal: {}", e);
+    }
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    if let Err(e) = saved {
+        eprintln!("Failed to save board: {}", e);
+    }
 
     if let Err(err) = res {
         println!("{:?}", err);
@@ -50,13 +162,13 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App, terminate: &AtomicBool) -> io::Result<()> {
     // Try to load saved board
-    match storage::load_board() {
+    match storage::load_first_board() {
         Ok(board) => {
             app.board = board;
             app.validate_selection(); // Ensure selection indices are valid after loading
-            app.status_message = "Board loaded successfully".to_string();
+            app.status_message = format!("Board loaded from {}", storage::database_path().display());
         }
         Err(_) => {
             app.status_message = "Starting with new board".to_string();
@@ -67,39 +179,63 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
     let mut last_tick = Instant::now();
 
     loop {
-        terminal.draw(|f| ui::ui(f, &app))?;
+        if terminate.load(Ordering::Relaxed) {
+            app.quit();
+            break;
+        }
+
+        terminal.draw(|f| ui::ui(f, app))?;
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
+        // Resize and focus events only need the redraw above
         if crossterm::event::poll(timeout)? {
             match event::read()? {
-                Event::Key(key) => {
-                    // Handle Ctrl+C to quit gracefully
-                    if key.code == KeyCode::Char('c') && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) {
-                        app.quit();
-                    } else {
-                        handle_key_events(key, &mut app);
-                    }
+                // Handle Ctrl+C to quit gracefully
+                Event::Key(key)
+                    if key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) =>
+                {
+                    app.quit();
                 }
-                _ => {} // Ignore other events like mouse, resize, etc.
+                Event::Key(key) => handle_key_events(key, app),
+                Event::Paste(text) => handle_paste(&text, app),
+                Event::Mouse(mouse) => handle_mouse_events(mouse, app),
+                _ => {}
             }
         }
 
+        if app.external_edit.is_some() {
+            let edited = match app.external_edit_document() {
+                Ok(document) => with_terminal_suspended(terminal, || external_editor::edit(&document))?,
+                Err(e) => Err(io::Error::new(io::ErrorKind::InvalidInput, e)),
+            };
+            app.finish_external_edit(edited);
+        }
+
         if last_tick.elapsed() >= tick_rate {
             app.tick();
             last_tick = Instant::now();
         }
 
         if app.should_quit {
-            // Save board before quitting
-            if let Err(e) = storage::save_board(&app.board) {
-                eprintln!("Failed to save board: {}", e);
-            }
             break;
         }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Hands the terminal to another program: raw mode and the alternate screen
+/// are switched off while `f` runs, then restored with a full redraw.
+fn with_terminal_suspended<B: Backend, T>(terminal: &mut Terminal<B>, f: impl FnOnce() -> T) -> io::Result<T> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
+    let result = f();
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
+    terminal.clear()?;
+    Ok(result)
+}
@@ -1,13 +1,47 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{column::Column, task::Task};
+use super::{
+    changes::ChangeSet,
+    column::{Column, WipPolicy},
+    event::{EventKind, TaskEvent},
+    filter::SavedView,
+    label::{Label, LabelColor},
+    task::{HiddenState, Task},
+};
+
+/// An archived or deleted task together with the column it came from.
+#[derive(Clone, Debug)]
+pub struct HiddenTask {
+    pub column_id: Uuid,
+    pub task: Task,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Board {
     pub id: Uuid,
     pub title: String,
+    /// Read freely, but change columns and their tasks through the methods
+    /// below so the edits are recorded in `changes` and get saved.
     pub columns: Vec<Column>,
+    /// Label registry shared by all tasks on this board.
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// Named filters saved for this board.
+    #[serde(default)]
+    pub views: Vec<SavedView>,
+    /// Archived and trashed tasks known this session: those hidden since the
+    /// board was loaded and those read back for the archive screen. Only the
+    /// ones marked in `changes` are written. Hidden tasks are not part of a
+    /// normal load; `storage::load_hidden_tasks` reads them for the archive.
+    /// Restoring is only possible for tasks listed here.
+    #[serde(skip)]
+    pub hidden: Vec<HiddenTask>,
+    /// Activity recorded since the last save, appended to the stored log.
+    #[serde(skip)]
+    pub events: Vec<TaskEvent>,
+    #[serde(skip)]
+    pub changes: ChangeSet,
 }
 
 impl Board {
@@ -16,6 +50,11 @@ impl Board {
             id: Uuid::new_v4(),
             title,
             columns: Vec::new(),
+            labels: Vec::new(),
+            views: Vec::new(),
+            hidden: Vec::new(),
+            events: Vec::new(),
+            changes: ChangeSet::full(),
         };
 
         board.columns.push(Column::new("To Do".to_string()));
@@ -25,41 +64,472 @@ impl Board {
         board
     }
 
+    pub fn is_dirty(&self) -> bool {
+        !self.changes.is_empty()
+    }
 
-    pub fn get_column_mut(&mut self, column_id: Uuid) -> Option<&mut Column> {
-        self.columns.iter_mut().find(|col| col.id == column_id)
+    pub fn rename(&mut self, title: String) {
+        self.title = title;
+        self.changes.mark_board();
+    }
+
+    /// The last column is treated as the "done" column: its cards no longer
+    /// count as overdue.
+    pub fn done_column_id(&self) -> Option<Uuid> {
+        self.columns.last().map(|col| col.id)
     }
 
+    pub fn get_label(&self, label_id: Uuid) -> Option<&Label> {
+        self.labels.iter().find(|label| label.id == label_id)
+    }
 
-    pub fn move_task(&mut self, task_id: Uuid, from_column_id: Uuid, to_column_id: Uuid) -> bool {
-        if from_column_id == to_column_id {
+    /// Looks a label up by name, ignoring case.
+    pub fn find_label(&self, name: &str) -> Option<&Label> {
+        self.labels
+            .iter()
+            .find(|label| label.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the id of the label called `name`, registering it with the
+    /// next palette color if it doesn't exist yet.
+    pub fn ensure_label(&mut self, name: &str) -> Uuid {
+        if let Some(label) = self.find_label(name) {
+            return label.id;
+        }
+
+        let color = LabelColor::PALETTE[self.labels.len() % LabelColor::PALETTE.len()];
+        let label = Label::new(name.to_string(), color);
+        let label_id = label.id;
+        self.labels.push(label);
+        self.changes.mark_label(label_id);
+        label_id
+    }
+
+    /// Renames a label. Refused for an empty name or one another label
+    /// already has, ignoring case.
+    pub fn rename_label(&mut self, label_id: Uuid, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.find_label(name).is_some_and(|label| label.id != label_id) {
+            return false;
+        }
+        let Some(label) = self.labels.iter_mut().find(|label| label.id == label_id) else {
+            return false;
+        };
+        label.name = name.to_string();
+        self.changes.mark_label(label_id);
+        true
+    }
+
+    pub fn set_label_color(&mut self, label_id: Uuid, color: LabelColor) -> bool {
+        let Some(label) = self.labels.iter_mut().find(|label| label.id == label_id) else {
+            return false;
+        };
+        label.color = color;
+        self.changes.mark_label(label_id);
+        true
+    }
+
+    /// Number of tasks carrying a label, archived and trashed ones since the
+    /// last save included.
+    pub fn label_uses(&self, label_id: Uuid) -> usize {
+        let hidden = self.hidden.iter().map(|hidden| &hidden.task);
+        self.columns
+            .iter()
+            .flat_map(|column| &column.tasks)
+            .chain(hidden)
+            .filter(|task| task.labels.contains(&label_id))
+            .count()
+    }
+
+    /// Removes a label no task uses and drops it from saved views. Returns
+    /// false if the label is unknown or still in use.
+    pub fn delete_label(&mut self, label_id: Uuid) -> bool {
+        let Some(index) = self.labels.iter().position(|label| label.id == label_id) else {
+            return false;
+        };
+        if self.label_uses(label_id) > 0 {
             return false;
         }
 
-        let task = {
-            if let Some(from_column) = self.get_column_mut(from_column_id) {
-                if let Some(task) = from_column.remove_task(task_id) {
-                    task
-                } else {
-                    return false;
+        self.labels.remove(index);
+        self.changes.delete_label(label_id);
+        for view in &mut self.views {
+            if view.filter.labels.contains(&label_id) {
+                view.filter.labels.retain(|id| *id != label_id);
+                self.changes.mark_view(view.id);
+            }
+        }
+        true
+    }
+
+    /// Saves a view, replacing an existing one with the same name.
+    pub fn save_view(&mut self, view: SavedView) -> Uuid {
+        let view_id = match self.views.iter_mut().find(|v| v.name.eq_ignore_ascii_case(&view.name)) {
+            Some(existing) => {
+                existing.filter = view.filter;
+                existing.id
+            }
+            None => {
+                let view_id = view.id;
+                self.views.push(view);
+                view_id
+            }
+        };
+        self.changes.mark_view(view_id);
+        view_id
+    }
+
+    pub fn delete_view(&mut self, view_id: Uuid) -> bool {
+        if let Some(index) = self.views.iter().position(|v| v.id == view_id) {
+            self.views.remove(index);
+            self.changes.delete_view(view_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn column_index(&self, column_id: Uuid) -> Option<usize> {
+        self.columns.iter().position(|col| col.id == column_id)
+    }
+
+    /// Inserts a new empty column at `index` (clamped to the column count).
+    pub fn insert_column(&mut self, index: usize, title: String) -> Uuid {
+        let column = Column::new(title);
+        let column_id = column.id;
+        let index = index.min(self.columns.len());
+        self.columns.insert(index, column);
+        self.changes.mark_column(column_id);
+        self.changes.mark_column_order();
+        column_id
+    }
+
+    pub fn rename_column(&mut self, column_id: Uuid, title: String) -> bool {
+        if let Some(column) = self.get_column_mut(column_id) {
+            column.title = title;
+            self.changes.mark_column(column_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sets or clears the WIP limit of a column.
+    pub fn set_wip_limit(&mut self, column_id: Uuid, limit: Option<usize>, policy: WipPolicy) -> bool {
+        if let Some(column) = self.get_column_mut(column_id) {
+            column.wip_limit = limit;
+            column.wip_policy = policy;
+            self.changes.mark_column(column_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether moving a task into the column is refused by a blocking WIP limit.
+    pub fn is_move_blocked(&self, to_column_id: Uuid) -> bool {
+        self.are_moves_blocked(to_column_id, 1)
+    }
+
+    /// Whether moving `count` tasks into the column at once is refused by a
+    /// blocking WIP limit.
+    pub fn are_moves_blocked(&self, to_column_id: Uuid, count: usize) -> bool {
+        self.columns
+            .iter()
+            .find(|column| column.id == to_column_id)
+            .is_some_and(|column| column.wip_policy == WipPolicy::Block && !column.has_room_for(count))
+    }
+
+    /// Removes a column. Its tasks are appended to `move_tasks_to` when given,
+    /// otherwise they go to the trash. Trashed tasks are filed under the
+    /// first remaining column, where restoring them puts them. Refused when
+    /// the tasks would overflow a blocking WIP limit on the target.
+    pub fn delete_column(&mut self, column_id: Uuid, move_tasks_to: Option<Uuid>) -> bool {
+        if move_tasks_to == Some(column_id) || move_tasks_to.is_some_and(|id| self.column_index(id).is_none()) {
+            return false;
+        }
+        let Some(index) = self.column_index(column_id) else {
+            return false;
+        };
+        if move_tasks_to.is_some_and(|id| self.are_moves_blocked(id, self.columns[index].tasks.len())) {
+            return false;
+        }
+        let Some(fallback_id) = self.columns.iter().map(|column| column.id).find(|id| *id != column_id) else {
+            return false;
+        };
+
+        let column = self.columns.remove(index);
+        match move_tasks_to {
+            Some(target_id) => {
+                for task in column.tasks {
+                    self.log(task.id, EventKind::Moved { from: column_id, to: target_id });
+                    self.add_task(target_id, task);
+                }
+            }
+            None => {
+                for mut task in column.tasks {
+                    let task_id = task.id;
+                    task.hide(HiddenState::Deleted);
+                    self.hidden.push(HiddenTask { column_id: fallback_id, task });
+                    self.log(task_id, EventKind::Deleted);
+                    self.changes.mark_task(task_id);
                 }
-            } else {
-                return false;
             }
+        }
+        self.changes.delete_column(column_id);
+        true
+    }
+
+    /// Moves a column to `index` (clamped to the column count).
+    pub fn move_column(&mut self, column_id: Uuid, index: usize) -> bool {
+        let Some(current) = self.column_index(column_id) else {
+            return false;
         };
+        let index = index.min(self.columns.len() - 1);
+        if current == index {
+            return false;
+        }
+
+        let column = self.columns.remove(current);
+        self.columns.insert(index, column);
+        self.changes.mark_column_order();
+        true
+    }
+
+    fn get_column_mut(&mut self, column_id: Uuid) -> Option<&mut Column> {
+        self.columns.iter_mut().find(|col| col.id == column_id)
+    }
 
-        if let Some(to_column) = self.get_column_mut(to_column_id) {
-            to_column.add_task(task);
+    pub fn add_task(&mut self, column_id: Uuid, task: Task) -> bool {
+        let task_id = task.id;
+        if let Some(column) = self.get_column_mut(column_id) {
+            column.add_task(task);
+            self.changes.mark_column(column_id);
+            self.changes.mark_task(task_id);
             true
         } else {
             false
         }
     }
 
+    /// Inserts a task at `index` in a column (clamped to the column length).
+    pub fn insert_task(&mut self, column_id: Uuid, index: usize, task: Task) -> bool {
+        let task_id = task.id;
+        if let Some(column) = self.get_column_mut(column_id) {
+            let index = index.min(column.tasks.len());
+            column.tasks.insert(index, task);
+            self.hidden.retain(|hidden| hidden.task.id != task_id);
+            self.changes.mark_column(column_id);
+            self.changes.mark_task(task_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Adds a new task and records its creation.
+    pub fn create_task(&mut self, column_id: Uuid, index: usize, task: Task) -> bool {
+        let task_id = task.id;
+        let created = self.insert_task(column_id, index, task);
+        if created {
+            self.log(task_id, EventKind::Created);
+        }
+        created
+    }
+
+    /// Moves a task so it ends up at `index` in the target column, which may
+    /// be the column it is already in. Moves into another column are refused
+    /// when its WIP limit blocks them.
+    pub fn move_task(&mut self, task_id: Uuid, to_column_id: Uuid, index: usize) -> bool {
+        let Some((from_column_id, _)) = self.locate_task(task_id) else {
+            return false;
+        };
+        if self.column_index(to_column_id).is_none() {
+            return false;
+        }
+        if from_column_id != to_column_id && self.is_move_blocked(to_column_id) {
+            return false;
+        }
+
+        if from_column_id == to_column_id {
+            let moved = self
+                .get_column_mut(from_column_id)
+                .is_some_and(|column| column.move_task_to(task_id, index));
+            if moved {
+                self.changes.mark_column(from_column_id);
+            }
+            return moved;
+        }
+
+        let Some(task) = self
+            .get_column_mut(from_column_id)
+            .and_then(|column| column.remove_task(task_id))
+        else {
+            return false;
+        };
+        self.changes.mark_column(from_column_id);
+        self.log(task_id, EventKind::Moved { from: from_column_id, to: to_column_id });
+        self.insert_task(to_column_id, index, task)
+    }
+
+    /// Takes a task off the board as archived or deleted. It keeps its
+    /// column so it can be restored there later.
+    pub fn hide_task(&mut self, task_id: Uuid, state: HiddenState) -> bool {
+        let Some((column_id, _)) = self.locate_task(task_id) else {
+            return false;
+        };
+        let Some(mut task) = self
+            .get_column_mut(column_id)
+            .and_then(|column| column.remove_task(task_id))
+        else {
+            return false;
+        };
+
+        task.hide(state);
+        self.hidden.push(HiddenTask { column_id, task });
+        self.log(
+            task_id,
+            match state {
+                HiddenState::Archived => EventKind::Archived,
+                HiddenState::Deleted => EventKind::Deleted,
+            },
+        );
+        self.changes.mark_column(column_id);
+        self.changes.mark_task(task_id);
+        true
+    }
+
+    /// Adds hidden tasks read from storage to `hidden`, skipping those
+    /// already there, so they can be restored.
+    pub fn remember_hidden(&mut self, tasks: &[HiddenTask]) {
+        for hidden in tasks {
+            if !self.hidden.iter().any(|known| known.task.id == hidden.task.id) {
+                self.hidden.push(hidden.clone());
+            }
+        }
+    }
+
+    /// Puts a hidden task back on the board at `index` in `column_id`.
+    /// Refused unless the task is in `hidden`, so a purged task can't come
+    /// back.
+    pub fn restore_task(&mut self, column_id: Uuid, index: usize, mut task: Task) -> bool {
+        let task_id = task.id;
+        if !self.hidden.iter().any(|hidden| hidden.task.id == task_id) || self.locate_task(task_id).is_some() {
+            return false;
+        }
+        task.unhide();
+        let restored = self.insert_task(column_id, index, task);
+        if restored {
+            self.log(task_id, EventKind::Restored);
+        }
+        restored
+    }
 
+    /// Permanently removes a hidden task from storage.
+    pub fn purge_task(&mut self, task_id: Uuid) {
+        self.hidden.retain(|hidden| hidden.task.id != task_id);
+        self.changes.delete_task(task_id);
+    }
+
+    /// Blockers of `task` that are on the board but not in the done column
+    /// yet. Archived or trashed blockers no longer hold the task up.
+    pub fn open_blockers(&self, task: &Task) -> Vec<&Task> {
+        let done_column = self.columns.last();
+        task.blocked_by
+            .iter()
+            .filter(|id| done_column.is_none_or(|column| column.task_index(**id).is_none()))
+            .filter_map(|id| self.get_task(*id))
+            .collect()
+    }
+
+    pub fn is_blocked(&self, task: &Task) -> bool {
+        !self.open_blockers(task).is_empty()
+    }
+
+    /// Column id and position of a task.
+    pub fn locate_task(&self, task_id: Uuid) -> Option<(Uuid, usize)> {
+        self.columns
+            .iter()
+            .find_map(|column| column.task_index(task_id).map(|index| (column.id, index)))
+    }
+
+    /// Overwrites the stored task that has the same id, logging every
+    /// field that changed.
+    pub fn replace_task(&mut self, task: Task) -> bool {
+        let Some(existing) = self.get_task(task.id) else {
+            return false;
+        };
+
+        let changes = self.field_changes(existing, &task);
+        for kind in changes {
+            self.log(task.id, kind);
+        }
+        if let Some(existing) = self.get_task_mut(task.id) {
+            *existing = task;
+        }
+        true
+    }
+
+    fn field_changes(&self, old: &Task, new: &Task) -> Vec<EventKind> {
+        let label_names = |task: &Task| {
+            task.labels
+                .iter()
+                .filter_map(|id| self.get_label(*id))
+                .map(|label| label.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let checklist = |task: &Task| {
+            task.checklist_progress()
+                .map(|(done, total)| format!("{}/{}", done, total))
+                .unwrap_or_default()
+        };
+        let blockers = |task: &Task| {
+            task.blocked_by
+                .iter()
+                .filter_map(|id| self.get_task(*id))
+                .map(|blocker| blocker.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let due_date = |task: &Task| {
+            task.due_date
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        };
+
+        [
+            ("title", old.title.clone(), new.title.clone()),
+            (
+                "description",
+                old.description.clone().unwrap_or_default(),
+                new.description.clone().unwrap_or_default(),
+            ),
+            ("due date", due_date(old), due_date(new)),
+            ("priority", old.priority.to_string(), new.priority.to_string()),
+            ("labels", label_names(old), label_names(new)),
+            ("checklist", checklist(old), checklist(new)),
+            ("blocked by", blockers(old), blockers(new)),
+        ]
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| EventKind::Changed {
+            field: field.to_string(),
+            old,
+            new,
+        })
+        .collect()
+    }
+
+    fn log(&mut self, task_id: Uuid, kind: EventKind) {
+        self.events.push(TaskEvent::new(task_id, kind));
+    }
+
+    /// Returns the task for modification and records it as changed.
     pub fn get_task_mut(&mut self, task_id: Uuid) -> Option<&mut Task> {
         for column in &mut self.columns {
             if let Some(task) = column.get_task_mut(task_id) {
+                self.changes.mark_task(task_id);
                 return Some(task);
             }
         }
@@ -75,13 +545,20 @@ impl Board {
         None
     }
 
+    /// Drops a task as if it had never been added, activity log included.
+    /// Only undoing an add does this; deleting from the board goes through
+    /// the trash.
     pub fn delete_task(&mut self, task_id: Uuid) -> bool {
         for column in &mut self.columns {
             if column.remove_task(task_id).is_some() {
+                self.changes.mark_column(column.id);
+                self.changes.delete_task(task_id);
+                self.changes.delete_events(task_id);
+                self.events.retain(|event| event.task_id != task_id);
                 return true;
             }
         }
         false
     }
 
-}
\ No newline at end of file
+}
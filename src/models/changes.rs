@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+/// Tracks which rows of a board differ from what was last persisted, so
+/// storage can upsert and delete only those rows instead of rewriting the
+/// whole board.
+#[derive(Clone, Debug, Default)]
+pub struct ChangeSet {
+    /// The board has never been saved, so every row must be written.
+    pub full: bool,
+    pub board: bool,
+    /// Columns were added, removed or reordered, so every column position
+    /// must be rewritten.
+    pub column_order: bool,
+    /// Columns whose row or task ordering changed. All tasks of a dirty
+    /// column are rewritten so their positions stay contiguous.
+    pub columns: HashSet<Uuid>,
+    pub tasks: HashSet<Uuid>,
+    pub labels: HashSet<Uuid>,
+    pub views: HashSet<Uuid>,
+    pub deleted_columns: HashSet<Uuid>,
+    pub deleted_tasks: HashSet<Uuid>,
+    pub deleted_views: HashSet<Uuid>,
+    pub deleted_labels: HashSet<Uuid>,
+    /// Tasks whose stored activity log is dropped, because adding them was
+    /// undone.
+    pub deleted_events: HashSet<Uuid>,
+    /// Bumped by every recorded change and kept across `clear`, so callers
+    /// can tell whether the board was edited since they last looked.
+    pub revision: u64,
+}
+
+impl ChangeSet {
+    pub fn full() -> Self {
+        Self {
+            full: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.full
+            && !self.board
+            && !self.column_order
+            && self.columns.is_empty()
+            && self.tasks.is_empty()
+            && self.labels.is_empty()
+            && self.views.is_empty()
+            && self.deleted_columns.is_empty()
+            && self.deleted_tasks.is_empty()
+            && self.deleted_views.is_empty()
+            && self.deleted_labels.is_empty()
+            && self.deleted_events.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self {
+            revision: self.revision,
+            ..Self::default()
+        };
+    }
+
+    pub fn mark_board(&mut self) {
+        self.revision += 1;
+        self.board = true;
+    }
+
+    pub fn mark_column_order(&mut self) {
+        self.revision += 1;
+        self.column_order = true;
+    }
+
+    pub fn mark_column(&mut self, column_id: Uuid) {
+        self.revision += 1;
+        self.columns.insert(column_id);
+    }
+
+    pub fn mark_task(&mut self, task_id: Uuid) {
+        self.revision += 1;
+        // A deleted task can come back through undo.
+        self.deleted_tasks.remove(&task_id);
+        self.tasks.insert(task_id);
+    }
+
+    pub fn mark_label(&mut self, label_id: Uuid) {
+        self.revision += 1;
+        self.labels.insert(label_id);
+    }
+
+    pub fn mark_view(&mut self, view_id: Uuid) {
+        self.revision += 1;
+        self.views.insert(view_id);
+    }
+
+    pub fn delete_column(&mut self, column_id: Uuid) {
+        self.revision += 1;
+        self.columns.remove(&column_id);
+        self.deleted_columns.insert(column_id);
+        self.column_order = true;
+    }
+
+    pub fn delete_task(&mut self, task_id: Uuid) {
+        self.revision += 1;
+        self.tasks.remove(&task_id);
+        self.deleted_tasks.insert(task_id);
+    }
+
+    pub fn delete_events(&mut self, task_id: Uuid) {
+        self.revision += 1;
+        self.deleted_events.insert(task_id);
+    }
+
+    pub fn delete_label(&mut self, label_id: Uuid) {
+        self.revision += 1;
+        self.labels.remove(&label_id);
+        self.deleted_labels.insert(label_id);
+    }
+
+    pub fn delete_view(&mut self, view_id: Uuid) {
+        self.revision += 1;
+        self.views.remove(&view_id);
+        self.deleted_views.insert(view_id);
+    }
+}
@@ -3,11 +3,43 @@ use uuid::Uuid;
 
 use super::task::Task;
 
+/// What happens when a move would take a column past its WIP limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WipPolicy {
+    /// Allow the move but flag the column.
+    #[default]
+    Warn,
+    /// Refuse the move.
+    Block,
+}
+
+impl WipPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WipPolicy::Warn => "warn",
+            WipPolicy::Block => "block",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "warn" => Some(WipPolicy::Warn),
+            "block" => Some(WipPolicy::Block),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Column {
     pub id: Uuid,
     pub title: String,
     pub tasks: Vec<Task>,
+    /// Maximum number of tasks the column should hold.
+    #[serde(default)]
+    pub wip_limit: Option<usize>,
+    #[serde(default)]
+    pub wip_policy: WipPolicy,
 }
 
 impl Column {
@@ -16,9 +48,20 @@ impl Column {
             id: Uuid::new_v4(),
             title,
             tasks: Vec::new(),
+            wip_limit: None,
+            wip_policy: WipPolicy::default(),
         }
     }
 
+    pub fn is_over_limit(&self) -> bool {
+        self.wip_limit.is_some_and(|limit| self.tasks.len() > limit)
+    }
+
+    /// Whether `count` more tasks fit within the limit.
+    pub fn has_room_for(&self, count: usize) -> bool {
+        self.wip_limit.is_none_or(|limit| self.tasks.len() + count <= limit)
+    }
+
     pub fn add_task(&mut self, task: Task) {
         self.tasks.push(task);
     }
@@ -31,6 +74,26 @@ impl Column {
         }
     }
 
+    pub fn task_index(&self, task_id: Uuid) -> Option<usize> {
+        self.tasks.iter().position(|task| task.id == task_id)
+    }
+
+    /// Moves a task to `index` (clamped to the column length), shifting the
+    /// tasks in between. Returns false if the task is missing or already there.
+    pub fn move_task_to(&mut self, task_id: Uuid, index: usize) -> bool {
+        let Some(current) = self.task_index(task_id) else {
+            return false;
+        };
+        let index = index.min(self.tasks.len() - 1);
+        if current == index {
+            return false;
+        }
+
+        let task = self.tasks.remove(current);
+        self.tasks.insert(index, task);
+        true
+    }
+
     pub fn get_task_mut(&mut self, task_id: Uuid) -> Option<&mut Task> {
         self.tasks.iter_mut().find(|task| task.id == task_id)
     }
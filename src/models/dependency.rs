@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::board::Board;
+
+/// "Blocked by" edges between the tasks on a board. Tasks that are archived,
+/// trashed or on another board are not part of the graph.
+#[derive(Clone, Debug, Default)]
+pub struct DependencyGraph {
+    blocked_by: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl DependencyGraph {
+    pub fn new(board: &Board) -> Self {
+        let blocked_by = board
+            .columns
+            .iter()
+            .flat_map(|column| &column.tasks)
+            .filter(|task| !task.blocked_by.is_empty())
+            .map(|task| (task.id, task.blocked_by.clone()))
+            .collect();
+        Self { blocked_by }
+    }
+
+    /// Whether making `task_id` wait for `blocker_id` would close a loop,
+    /// i.e. the blocker already waits for the task, directly or through
+    /// other tasks.
+    pub fn would_create_cycle(&self, task_id: Uuid, blocker_id: Uuid) -> bool {
+        let mut pending = vec![blocker_id];
+        let mut seen = HashSet::new();
+        while let Some(id) = pending.pop() {
+            if id == task_id {
+                return true;
+            }
+            if seen.insert(id)
+                && let Some(blockers) = self.blocked_by.get(&id)
+            {
+                pending.extend(blockers.iter().copied());
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::{HiddenState, Task};
+
+    /// Adds a task to the first column that waits for `blocked_by`.
+    fn add(board: &mut Board, title: &str, blocked_by: &[Uuid]) -> Uuid {
+        let mut task = Task::new(title.to_string());
+        task.blocked_by = blocked_by.to_vec();
+        let task_id = task.id;
+        board.create_task(board.columns[0].id, usize::MAX, task);
+        task_id
+    }
+
+    #[test]
+    fn a_task_cannot_wait_for_itself() {
+        let mut board = Board::new("Test".to_string());
+        let a = add(&mut board, "a", &[]);
+        assert!(DependencyGraph::new(&board).would_create_cycle(a, a));
+    }
+
+    #[test]
+    fn direct_cycles_are_detected() {
+        let mut board = Board::new("Test".to_string());
+        let b = add(&mut board, "b", &[]);
+        let a = add(&mut board, "a", &[b]);
+
+        let graph = DependencyGraph::new(&board);
+        assert!(graph.would_create_cycle(b, a));
+        assert!(!graph.would_create_cycle(a, b));
+    }
+
+    #[test]
+    fn transitive_cycles_are_detected() {
+        let mut board = Board::new("Test".to_string());
+        let c = add(&mut board, "c", &[]);
+        let b = add(&mut board, "b", &[c]);
+        let a = add(&mut board, "a", &[b]);
+        let other = add(&mut board, "other", &[]);
+
+        let graph = DependencyGraph::new(&board);
+        assert!(graph.would_create_cycle(c, a));
+        assert!(graph.would_create_cycle(c, b));
+        assert!(!graph.would_create_cycle(a, c));
+        assert!(!graph.would_create_cycle(c, other));
+    }
+
+    #[test]
+    fn done_archived_and_trashed_blockers_no_longer_block() {
+        let mut board = Board::new("Test".to_string());
+        let open = add(&mut board, "open", &[]);
+        let done = add(&mut board, "done", &[]);
+        let archived = add(&mut board, "archived", &[]);
+        let trashed = add(&mut board, "trashed", &[]);
+        let waiting = add(&mut board, "waiting", &[done, archived, trashed]);
+
+        let done_column_id = board.columns[2].id;
+        board.move_task(done, done_column_id, 0);
+        board.hide_task(archived, HiddenState::Archived);
+        board.hide_task(trashed, HiddenState::Deleted);
+        let task = board.get_task(waiting).unwrap();
+        assert!(!board.is_blocked(task));
+
+        let mut task = task.clone();
+        task.blocked_by.push(open);
+        let blockers: Vec<Uuid> = board.open_blockers(&task).iter().map(|task| task.id).collect();
+        assert_eq!(blockers, [open]);
+        assert!(board.is_blocked(&task));
+    }
+}
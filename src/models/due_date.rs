@@ -0,0 +1,206 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc, Weekday};
+
+/// Parses what the user typed into the due date field.
+///
+/// Accepts absolute dates (`2026-11-03`, `2026/11/03`, `11/03`), keywords
+/// (`today`, `tomorrow`, `next week`, `next month`), weekdays (`fri`,
+/// `next friday`: the next such day after `today`) and offsets (`+3d`, `+2w`,
+/// `+1m`, `in 3 days`). An empty input, `none` or `clear` removes the date.
+pub fn parse_due_date(input: &str, today: NaiveDate) -> Result<Option<NaiveDate>, String> {
+    let input = input.trim().to_lowercase();
+    let input = input.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match input.as_str() {
+        "" | "none" | "clear" | "-" => return Ok(None),
+        "today" | "tod" => return Ok(Some(today)),
+        "tomorrow" | "tmr" | "tom" => return Ok(today.checked_add_days(Days::new(1))),
+        "yesterday" => return Ok(today.checked_sub_days(Days::new(1))),
+        "next week" => return Ok(today.checked_add_days(Days::new(7))),
+        "next month" => return Ok(today.checked_add_months(Months::new(1))),
+        _ => {}
+    }
+
+    if let Some(date) = parse_absolute(&input, today) {
+        return Ok(Some(date));
+    }
+
+    let weekday_name = input.strip_prefix("next ").unwrap_or(&input);
+    if let Some(weekday) = parse_weekday(weekday_name) {
+        let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+        let ahead = if ahead == 0 { 7 } else { ahead };
+        return Ok(today.checked_add_days(Days::new(ahead as u64)));
+    }
+
+    let offset = input
+        .strip_prefix('+')
+        .or_else(|| input.strip_prefix("in "))
+        .and_then(|rest| parse_offset(rest, today));
+    if let Some(date) = offset {
+        return Ok(Some(date));
+    }
+
+    Err(format!("Unrecognized date: {}", input))
+}
+
+fn parse_absolute(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    for format in ["%Y-%m-%d", "%Y/%m/%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(input, format) {
+            return Some(date);
+        }
+    }
+
+    // Month and day only: the next occurrence, today included.
+    let (month, day) = input.split_once('/')?;
+    let (month, day) = (month.parse().ok()?, day.parse().ok()?);
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    let weekday = match input {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// Parses `3d`, `2w`, `1m`, `3 days`, `2 weeks` or `1 month`.
+fn parse_offset(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let digits_end = input.find(|c: char| !c.is_ascii_digit())?;
+    let amount: u32 = input[..digits_end].parse().ok()?;
+
+    match input[digits_end..].trim() {
+        "d" | "day" | "days" => today.checked_add_days(Days::new(amount as u64)),
+        "w" | "week" | "weeks" => today.checked_add_days(Days::new(amount as u64 * 7)),
+        "m" | "month" | "months" => today.checked_add_months(Months::new(amount)),
+        _ => None,
+    }
+}
+
+/// Due dates are calendar days; they are stored as midnight UTC of that day.
+pub fn to_due_datetime(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// Human readable preview of a parsed date relative to `today`.
+pub fn describe_due_date(date: NaiveDate, today: NaiveDate) -> String {
+    let days = (date - today).num_days();
+    let relative = match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        d if d > 1 => format!("in {} days", d),
+        d => format!("{} days ago", -d),
+    };
+    format!("{} ({})", date.format("%a %Y-%m-%d"), relative)
+}
+
+/// How close a due date is, used for highlighting cards and reminders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DueStatus {
+    Overdue,
+    Today,
+    /// Due within the next seven days.
+    ThisWeek,
+    Later,
+}
+
+pub fn due_status(due: NaiveDate, today: NaiveDate) -> DueStatus {
+    match (due - today).num_days() {
+        d if d < 0 => DueStatus::Overdue,
+        0 => DueStatus::Today,
+        1..=7 => DueStatus::ThisWeek,
+        _ => DueStatus::Later,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Wednesday.
+    fn today() -> NaiveDate {
+        date(2026, 10, 14)
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn parse(input: &str) -> Option<NaiveDate> {
+        parse_due_date(input, today()).unwrap_or_else(|e| panic!("{:?}: {}", input, e))
+    }
+
+    #[test]
+    fn empty_input_clears_the_date() {
+        for input in ["", "  ", "none", "Clear", "-"] {
+            assert_eq!(parse(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(parse("today"), Some(today()));
+        assert_eq!(parse(" Tomorrow "), Some(date(2026, 10, 15)));
+        assert_eq!(parse("tmr"), Some(date(2026, 10, 15)));
+        assert_eq!(parse("yesterday"), Some(date(2026, 10, 13)));
+        assert_eq!(parse("next   week"), Some(date(2026, 10, 21)));
+        assert_eq!(parse("next month"), Some(date(2026, 11, 14)));
+    }
+
+    #[test]
+    fn weekdays_are_the_next_such_day_after_today() {
+        assert_eq!(parse("fri"), Some(date(2026, 10, 16)));
+        assert_eq!(parse("next friday"), Some(date(2026, 10, 16)));
+        assert_eq!(parse("Monday"), Some(date(2026, 10, 19)));
+        // Today's weekday means a week from now
+        assert_eq!(parse("wed"), Some(date(2026, 10, 21)));
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(parse("+3d"), Some(date(2026, 10, 17)));
+        assert_eq!(parse("+2w"), Some(date(2026, 10, 28)));
+        assert_eq!(parse("+1m"), Some(date(2026, 11, 14)));
+        assert_eq!(parse("+0d"), Some(today()));
+        assert_eq!(parse("in 3 days"), Some(date(2026, 10, 17)));
+        assert_eq!(parse("in 1 week"), Some(date(2026, 10, 21)));
+        assert_eq!(parse("in 2 months"), Some(date(2026, 12, 14)));
+    }
+
+    #[test]
+    fn month_offsets_clamp_to_the_end_of_shorter_months() {
+        let january_end = date(2026, 1, 31);
+        assert_eq!(parse_due_date("+1m", january_end), Ok(Some(date(2026, 2, 28))));
+        assert_eq!(parse_due_date("next month", january_end), Ok(Some(date(2026, 2, 28))));
+        assert_eq!(parse_due_date("+1m", date(2028, 1, 31)), Ok(Some(date(2028, 2, 29))));
+    }
+
+    #[test]
+    fn absolute_dates() {
+        assert_eq!(parse("2026-11-03"), Some(date(2026, 11, 3)));
+        assert_eq!(parse("2025/01/02"), Some(date(2025, 1, 2)));
+        // Month and day only roll over to next year once passed
+        assert_eq!(parse("11/03"), Some(date(2026, 11, 3)));
+        assert_eq!(parse("10/14"), Some(today()));
+        assert_eq!(parse("10/13"), Some(date(2027, 10, 13)));
+    }
+
+    #[test]
+    fn invalid_input_is_rejected() {
+        for input in ["someday", "2026-02-30", "13/01", "+3", "+d", "+3y", "in days", "next fortnight"] {
+            let error = parse_due_date(input, today()).unwrap_err();
+            assert!(error.starts_with("Unrecognized date"), "{:?} gave {:?}", input, error);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// What happened to a task. Moves refer to columns by id so they stay
+/// meaningful after a column is renamed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Created,
+    Changed { field: String, old: String, new: String },
+    Moved { from: Uuid, to: Uuid },
+    Archived,
+    Deleted,
+    Restored,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Created => "created",
+            EventKind::Changed { .. } => "changed",
+            EventKind::Moved { .. } => "moved",
+            EventKind::Archived => "archived",
+            EventKind::Deleted => "deleted",
+            EventKind::Restored => "restored",
+        }
+    }
+}
+
+/// One entry of a task's append-only activity log.
+#[derive(Clone, Debug)]
+pub struct TaskEvent {
+    pub task_id: Uuid,
+    pub at: DateTime<Utc>,
+    pub kind: EventKind,
+}
+
+impl TaskEvent {
+    pub fn new(task_id: Uuid, kind: EventKind) -> Self {
+        Self {
+            task_id,
+            at: Utc::now(),
+            kind,
+        }
+    }
+}
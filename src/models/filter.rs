@@ -0,0 +1,268 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    board::Board,
+    due_date::DueStatus,
+    task::{Priority, Task},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DueFilter {
+    Overdue,
+    Today,
+    /// Due today or within the next seven days.
+    ThisWeek,
+    NoDueDate,
+}
+
+impl DueFilter {
+    /// Cycles none → overdue → today → this week → no due date → none.
+    pub fn cycle(current: Option<DueFilter>) -> Option<DueFilter> {
+        match current {
+            None => Some(DueFilter::Overdue),
+            Some(DueFilter::Overdue) => Some(DueFilter::Today),
+            Some(DueFilter::Today) => Some(DueFilter::ThisWeek),
+            Some(DueFilter::ThisWeek) => Some(DueFilter::NoDueDate),
+            Some(DueFilter::NoDueDate) => None,
+        }
+    }
+
+    fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        matches!(
+            (self, task.due_status(today)),
+            (DueFilter::Overdue, Some(DueStatus::Overdue))
+                | (DueFilter::Today, Some(DueStatus::Today))
+                | (DueFilter::ThisWeek, Some(DueStatus::Today | DueStatus::ThisWeek))
+                | (DueFilter::NoDueDate, None)
+        )
+    }
+}
+
+impl std::fmt::Display for DueFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DueFilter::Overdue => write!(f, "overdue"),
+            DueFilter::Today => write!(f, "due today"),
+            DueFilter::ThisWeek => write!(f, "due this week"),
+            DueFilter::NoDueDate => write!(f, "no due date"),
+        }
+    }
+}
+
+/// Limits which tasks the board shows. Every criterion that is set must
+/// match; an empty filter shows everything.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskFilter {
+    /// Any of these priorities; empty means any priority.
+    #[serde(default)]
+    pub priorities: Vec<Priority>,
+    /// Any of these labels; empty means labels are ignored.
+    #[serde(default)]
+    pub labels: Vec<Uuid>,
+    #[serde(default)]
+    pub due: Option<DueFilter>,
+    /// Case-insensitive text that must appear in the title or description.
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub no_description: bool,
+}
+
+impl TaskFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn toggle_priority(&mut self, priority: Priority) {
+        if let Some(index) = self.priorities.iter().position(|p| *p == priority) {
+            self.priorities.remove(index);
+        } else {
+            self.priorities.push(priority);
+        }
+    }
+
+    pub fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        if !self.priorities.is_empty() && !self.priorities.contains(&task.priority) {
+            return false;
+        }
+
+        if !self.labels.is_empty() && !self.labels.iter().any(|label| task.labels.contains(label)) {
+            return false;
+        }
+
+        if let Some(due) = self.due
+            && !due.matches(task, today)
+        {
+            return false;
+        }
+
+        if self.no_description && task.description.as_ref().is_some_and(|desc| !desc.trim().is_empty()) {
+            return false;
+        }
+
+        let text = self.text.trim().to_lowercase();
+        if !text.is_empty() {
+            let in_title = task.title.to_lowercase().contains(&text);
+            let in_description = task
+                .description
+                .as_ref()
+                .is_some_and(|desc| desc.to_lowercase().contains(&text));
+            if !in_title && !in_description {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Short summary for the header, e.g. `Critical/High · due this week`.
+    pub fn describe(&self, board: &Board) -> String {
+        let mut parts = Vec::new();
+
+        if !self.priorities.is_empty() {
+            let names: Vec<String> = self.priorities.iter().map(|p| p.to_string()).collect();
+            parts.push(names.join("/"));
+        }
+        if !self.labels.is_empty() {
+            let names: Vec<String> = self
+                .labels
+                .iter()
+                .filter_map(|id| board.get_label(*id))
+                .map(|label| format!("#{}", label.name))
+                .collect();
+            parts.push(names.join(" "));
+        }
+        if let Some(due) = self.due {
+            parts.push(due.to_string());
+        }
+        if self.no_description {
+            parts.push("no description".to_string());
+        }
+        if !self.text.trim().is_empty() {
+            parts.push(format!("\"{}\"", self.text.trim()));
+        }
+
+        parts.join(" · ")
+    }
+}
+
+/// A filter saved under a name so it can be reapplied later.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub name: String,
+    pub filter: TaskFilter,
+}
+
+impl SavedView {
+    pub fn new(name: String, filter: TaskFilter) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            filter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::due_date::to_due_datetime;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+    }
+
+    fn task(title: &str, priority: Priority, due_in_days: Option<u64>) -> Task {
+        let due = due_in_days.map(|days| to_due_datetime(today() + chrono::Days::new(days)));
+        Task::new(title.to_string()).with_priority(priority).with_due_date(due)
+    }
+
+    fn matching<'a>(filter: &TaskFilter, tasks: &'a [Task]) -> Vec<&'a str> {
+        tasks
+            .iter()
+            .filter(|task| filter.matches(task, today()))
+            .map(|task| task.title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let tasks = [task("a", Priority::Low, None), task("b", Priority::High, Some(3))];
+        assert_eq!(matching(&TaskFilter::default(), &tasks), ["a", "b"]);
+    }
+
+    #[test]
+    fn priorities_combine_with_the_due_window() {
+        let tasks = [
+            task("critical soon", Priority::Critical, Some(2)),
+            task("high today", Priority::High, Some(0)),
+            task("high later", Priority::High, Some(30)),
+            task("low soon", Priority::Low, Some(1)),
+            task("critical undated", Priority::Critical, None),
+        ];
+        let mut filter = TaskFilter::default();
+        filter.toggle_priority(Priority::Critical);
+        filter.toggle_priority(Priority::High);
+        filter.due = Some(DueFilter::ThisWeek);
+
+        assert_eq!(matching(&filter, &tasks), ["critical soon", "high today"]);
+
+        filter.due = Some(DueFilter::NoDueDate);
+        assert_eq!(matching(&filter, &tasks), ["critical undated"]);
+    }
+
+    #[test]
+    fn toggling_a_priority_twice_removes_it() {
+        let mut filter = TaskFilter::default();
+        filter.toggle_priority(Priority::Low);
+        filter.toggle_priority(Priority::Low);
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn labels_match_any_of_the_chosen_ones() {
+        let (bug, ui, docs) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut tasks = [
+            task("bug", Priority::Medium, None),
+            task("ui", Priority::High, None),
+            task("docs", Priority::Medium, None),
+        ];
+        tasks[0].labels.push(bug);
+        tasks[1].labels.extend([bug, ui]);
+        tasks[2].labels.push(docs);
+
+        let filter = TaskFilter { labels: vec![bug, ui], ..TaskFilter::default() };
+        assert_eq!(matching(&filter, &tasks), ["bug", "ui"]);
+
+        let filter = TaskFilter { labels: vec![bug], priorities: vec![Priority::High], ..TaskFilter::default() };
+        assert_eq!(matching(&filter, &tasks), ["ui"]);
+    }
+
+    #[test]
+    fn text_searches_title_and_description_ignoring_case() {
+        let tasks = [
+            task("Fix LOGIN", Priority::Medium, None),
+            task("Other", Priority::Medium, None).with_description("the login page".to_string()),
+            task("Unrelated", Priority::Medium, None),
+        ];
+        let filter = TaskFilter { text: "  login ".to_string(), ..TaskFilter::default() };
+        assert_eq!(matching(&filter, &tasks), ["Fix LOGIN", "Other"]);
+    }
+
+    #[test]
+    fn no_description_treats_blank_descriptions_as_missing() {
+        let tasks = [
+            task("none", Priority::Medium, None),
+            task("blank", Priority::Medium, None).with_description("  \n".to_string()),
+            task("written", Priority::Medium, None).with_description("notes".to_string()),
+        ];
+        let filter = TaskFilter { no_description: true, ..TaskFilter::default() };
+        assert_eq!(matching(&filter, &tasks), ["none", "blank"]);
+
+        let filter = TaskFilter { no_description: true, text: "none".to_string(), ..TaskFilter::default() };
+        assert_eq!(matching(&filter, &tasks), ["none"]);
+    }
+}
@@ -0,0 +1,372 @@
+use std::collections::VecDeque;
+
+use uuid::Uuid;
+
+use super::{
+    board::Board,
+    task::{HiddenState, Task},
+};
+
+/// How many commands `History` keeps before dropping the oldest.
+const HISTORY_LIMIT: usize = 100;
+
+/// A reversible board mutation. Positions are `(column id, index)` pairs;
+/// the index of a move target is where the task ends up.
+#[derive(Clone, Debug)]
+pub enum Command {
+    Add { column_id: Uuid, index: usize, task: Task },
+    /// Drops a task without sending it to the trash; only used to undo `Add`.
+    Remove { column_id: Uuid, index: usize, task: Task },
+    /// Archives or trashes a task. `task` is the task as it was on the board.
+    Hide { column_id: Uuid, index: usize, task: Task, state: HiddenState },
+    Restore { column_id: Uuid, index: usize, task: Task, state: HiddenState },
+    Update { before: Task, after: Task },
+    Move { task_id: Uuid, from: (Uuid, usize), to: (Uuid, usize) },
+    /// Several commands undone and redone as one step. Each position is
+    /// taken after the commands before it have been applied.
+    Batch(Vec<Command>),
+}
+
+impl Command {
+    pub fn apply(&self, board: &mut Board) -> bool {
+        self.run(board, false)
+    }
+
+    /// Applies the command again for undo or redo. Moves, edits and hiding
+    /// are logged as usual since they really happen again, but re-adding a
+    /// task doesn't log a second creation.
+    pub fn replay(&self, board: &mut Board) -> bool {
+        self.run(board, true)
+    }
+
+    fn run(&self, board: &mut Board, replay: bool) -> bool {
+        match self {
+            Command::Add { column_id, index, task } if replay => board.insert_task(*column_id, *index, task.clone()),
+            Command::Add { column_id, index, task } => board.create_task(*column_id, *index, task.clone()),
+            Command::Remove { task, .. } => board.delete_task(task.id),
+            Command::Hide { task, state, .. } => board.hide_task(task.id, *state),
+            Command::Restore { column_id, index, task, .. } => board.restore_task(*column_id, *index, task.clone()),
+            Command::Update { after, .. } => board.replace_task(after.clone()),
+            Command::Move { task_id, to, .. } => board.move_task(*task_id, to.0, to.1),
+            Command::Batch(commands) => {
+                // All or nothing: if a step fails, take back the ones before it
+                // along with their log entries.
+                let events = board.events.len();
+                for (applied, command) in commands.iter().enumerate() {
+                    if !command.run(board, replay) {
+                        for command in commands[..applied].iter().rev() {
+                            command.inverse().run(board, true);
+                        }
+                        board.events.truncate(events);
+                        return false;
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    /// The command that undoes this one.
+    pub fn inverse(&self) -> Command {
+        match self.clone() {
+            Command::Add { column_id, index, task } => Command::Remove { column_id, index, task },
+            Command::Remove { column_id, index, task } => Command::Add { column_id, index, task },
+            Command::Hide { column_id, index, task, state } => Command::Restore { column_id, index, task, state },
+            Command::Restore { column_id, index, task, state } => Command::Hide { column_id, index, task, state },
+            Command::Update { before, after } => Command::Update {
+                before: after,
+                after: before,
+            },
+            Command::Move { task_id, from, to } => Command::Move {
+                task_id,
+                from: to,
+                to: from,
+            },
+            Command::Batch(commands) => Command::Batch(commands.iter().rev().map(Command::inverse).collect()),
+        }
+    }
+
+    /// Whether the command touches the task.
+    fn mentions(&self, task_id: Uuid) -> bool {
+        match self {
+            Command::Batch(commands) => commands.iter().any(|command| command.mentions(task_id)),
+            Command::Update { before, after } => before.id == task_id || after.id == task_id,
+            _ => self.task_id() == Some(task_id),
+        }
+    }
+
+    /// The task to select after undoing or redoing this command.
+    pub fn task_id(&self) -> Option<Uuid> {
+        match self {
+            Command::Add { task, .. }
+            | Command::Remove { task, .. }
+            | Command::Hide { task, .. }
+            | Command::Restore { task, .. } => Some(task.id),
+            Command::Update { after, .. } => Some(after.id),
+            Command::Move { task_id, .. } => Some(*task_id),
+            Command::Batch(commands) => commands.first().and_then(Command::task_id),
+        }
+    }
+
+    fn action(&self) -> &'static str {
+        match self {
+            Command::Add { .. } => "add",
+            Command::Remove { .. } => "remove",
+            Command::Hide { state: HiddenState::Archived, .. } => "archive",
+            Command::Hide { state: HiddenState::Deleted, .. } => "delete",
+            Command::Restore { .. } => "restore",
+            Command::Update { .. } => "edit",
+            Command::Move { from, to, .. } if from.0 == to.0 => "reorder",
+            Command::Move { .. } => "move",
+            Command::Batch(commands) => commands.first().map_or("change", Command::action),
+        }
+    }
+
+    /// Short description for the status bar, e.g. `delete "Write docs"`.
+    pub fn describe(&self, board: &Board) -> String {
+        let title = match self {
+            Command::Add { task, .. }
+            | Command::Remove { task, .. }
+            | Command::Hide { task, .. }
+            | Command::Restore { task, .. } => Some(task.title.as_str()),
+            Command::Update { after, .. } => Some(after.title.as_str()),
+            Command::Move { task_id, .. } => board.get_task(*task_id).map(|task| task.title.as_str()),
+            Command::Batch(commands) => return format!("{} {} tasks", self.action(), commands.len()),
+        }
+        .unwrap_or("task");
+
+        format!("{} \"{}\"", self.action(), title)
+    }
+}
+
+/// Bounded undo/redo stacks of applied commands.
+#[derive(Debug, Default)]
+pub struct History {
+    undo: VecDeque<Command>,
+    redo: Vec<Command>,
+}
+
+impl History {
+    /// Records a command that was just applied. A new change makes the
+    /// redo stack meaningless, so it is cleared.
+    pub fn record(&mut self, command: Command) {
+        self.redo.clear();
+        self.push_undo(command);
+    }
+
+    pub fn pop_undo(&mut self) -> Option<Command> {
+        self.undo.pop_back()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<Command> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, command: Command) {
+        self.undo.push_back(command);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+    }
+
+    pub fn push_redo(&mut self, command: Command) {
+        self.redo.push(command);
+    }
+
+    /// Drops every command that touches the task, for when it is gone for
+    /// good.
+    pub fn forget(&mut self, task_id: Uuid) {
+        self.undo.retain(|command| !command.mentions(task_id));
+        self.redo.retain(|command| !command.mentions(task_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::event::EventKind;
+
+    fn board_with_tasks(titles: &[&str]) -> (Board, Uuid) {
+        let mut board = Board::new("Test".to_string());
+        let column_id = board.columns[0].id;
+        for title in titles {
+            board.create_task(column_id, usize::MAX, Task::new(title.to_string()));
+        }
+        board.events.clear();
+        (board, column_id)
+    }
+
+    fn titles(board: &Board, column: usize) -> Vec<&str> {
+        board.columns[column].tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    fn created_events(board: &Board, task_id: Uuid) -> usize {
+        board
+            .events
+            .iter()
+            .filter(|event| event.task_id == task_id && matches!(event.kind, EventKind::Created))
+            .count()
+    }
+
+    #[test]
+    fn add_is_logged_once_across_undo_and_redo() {
+        let (mut board, column_id) = board_with_tasks(&[]);
+        let task = Task::new("New".to_string());
+        let task_id = task.id;
+        let add = Command::Add { column_id, index: 0, task };
+
+        assert!(add.apply(&mut board));
+        assert_eq!(created_events(&board, task_id), 1);
+
+        assert!(add.inverse().replay(&mut board));
+        assert!(board.get_task(task_id).is_none());
+        assert!(board.events.iter().all(|event| event.task_id != task_id));
+        assert!(board.changes.deleted_events.contains(&task_id));
+
+        assert!(add.replay(&mut board));
+        assert!(board.get_task(task_id).is_some());
+        assert_eq!(created_events(&board, task_id), 0);
+    }
+
+    #[test]
+    fn move_round_trip() {
+        let (mut board, column_id) = board_with_tasks(&["a", "b", "c"]);
+        let target_id = board.columns[1].id;
+        let task_id = board.columns[0].tasks[1].id;
+        let command = Command::Move { task_id, from: (column_id, 1), to: (target_id, 0) };
+
+        assert!(command.apply(&mut board));
+        assert_eq!((titles(&board, 0), titles(&board, 1)), (vec!["a", "c"], vec!["b"]));
+        assert!(command.inverse().replay(&mut board));
+        assert_eq!((titles(&board, 0), titles(&board, 1)), (vec!["a", "b", "c"], vec![]));
+    }
+
+    #[test]
+    fn reorder_clamps_out_of_range_indices_and_undoes_to_the_old_slot() {
+        let (mut board, column_id) = board_with_tasks(&["a", "b", "c"]);
+        let task_id = board.columns[0].tasks[0].id;
+        let to_bottom = Command::Move { task_id, from: (column_id, 0), to: (column_id, 99) };
+
+        assert!(to_bottom.apply(&mut board));
+        assert_eq!(titles(&board, 0), ["b", "c", "a"]);
+        assert!(to_bottom.inverse().replay(&mut board));
+        assert_eq!(titles(&board, 0), ["a", "b", "c"]);
+
+        let task_id = board.columns[0].tasks[2].id;
+        let to_top = Command::Move { task_id, from: (column_id, 2), to: (column_id, 0) };
+        assert!(to_top.apply(&mut board));
+        assert_eq!(titles(&board, 0), ["c", "a", "b"]);
+        assert!(to_top.inverse().replay(&mut board));
+        assert_eq!(titles(&board, 0), ["a", "b", "c"]);
+
+        // Already last, so an index past the end changes nothing
+        let stay = Command::Move { task_id, from: (column_id, 2), to: (column_id, usize::MAX) };
+        assert!(!stay.apply(&mut board));
+        assert_eq!(titles(&board, 0), ["a", "b", "c"]);
+
+        let across = Command::Move { task_id, from: (column_id, 2), to: (board.columns[1].id, 99) };
+        assert!(across.apply(&mut board));
+        assert_eq!(titles(&board, 1), ["c"]);
+    }
+
+    #[test]
+    fn update_round_trip() {
+        let (mut board, _) = board_with_tasks(&["before"]);
+        let before = board.columns[0].tasks[0].clone();
+        let mut after = before.clone();
+        after.title = "after".to_string();
+        let command = Command::Update { before, after };
+
+        assert!(command.apply(&mut board));
+        assert_eq!(titles(&board, 0), ["after"]);
+        assert!(command.inverse().replay(&mut board));
+        assert_eq!(titles(&board, 0), ["before"]);
+    }
+
+    #[test]
+    fn hide_round_trip_restores_the_position() {
+        let (mut board, column_id) = board_with_tasks(&["a", "b", "c"]);
+        let task = board.columns[0].tasks[1].clone();
+        let command = Command::Hide { column_id, index: 1, task, state: HiddenState::Archived };
+
+        assert!(command.apply(&mut board));
+        assert_eq!(titles(&board, 0), ["a", "c"]);
+        assert_eq!(board.hidden.len(), 1);
+        assert!(command.inverse().replay(&mut board));
+        assert_eq!(titles(&board, 0), ["a", "b", "c"]);
+        assert!(board.hidden.is_empty());
+    }
+
+    fn archive_all(board: &Board, column_id: Uuid) -> Command {
+        // Each task is first in the column once the ones before it are gone
+        let commands = board.columns[0]
+            .tasks
+            .iter()
+            .map(|task| Command::Hide { column_id, index: 0, task: task.clone(), state: HiddenState::Archived })
+            .collect();
+        Command::Batch(commands)
+    }
+
+    #[test]
+    fn undoing_a_batch_restores_the_order() {
+        let (mut board, column_id) = board_with_tasks(&["a", "b", "c"]);
+        let batch = archive_all(&board, column_id);
+
+        assert!(batch.apply(&mut board));
+        assert!(titles(&board, 0).is_empty());
+        assert!(batch.inverse().replay(&mut board));
+        assert_eq!(titles(&board, 0), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn failing_batch_leaves_the_board_unchanged() {
+        let (mut board, column_id) = board_with_tasks(&["a", "b", "c"]);
+        let mut batch = archive_all(&board, column_id);
+        if let Command::Batch(commands) = &mut batch {
+            commands.push(Command::Hide {
+                column_id,
+                index: 0,
+                task: Task::new("not on the board".to_string()),
+                state: HiddenState::Archived,
+            });
+        }
+
+        assert!(!batch.apply(&mut board));
+        assert_eq!(titles(&board, 0), ["a", "b", "c"]);
+        assert!(board.hidden.is_empty());
+        assert!(board.events.is_empty());
+    }
+
+    #[test]
+    fn history_keeps_the_latest_commands_up_to_the_limit() {
+        let (board, column_id) = board_with_tasks(&[]);
+        let mut history = History::default();
+        let commands: Vec<Command> = (0..HISTORY_LIMIT + 5)
+            .map(|i| Command::Add { column_id, index: 0, task: Task::new(i.to_string()) })
+            .collect();
+        for command in &commands {
+            history.record(command.clone());
+        }
+
+        let mut undone = Vec::new();
+        while let Some(command) = history.pop_undo() {
+            undone.push(command.describe(&board));
+        }
+        assert_eq!(undone.len(), HISTORY_LIMIT);
+        assert_eq!(undone.first().unwrap(), &format!("add \"{}\"", HISTORY_LIMIT + 4));
+        assert_eq!(undone.last().unwrap(), "add \"5\"");
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let (_, column_id) = board_with_tasks(&[]);
+        let add = |title: &str| Command::Add { column_id, index: 0, task: Task::new(title.to_string()) };
+        let mut history = History::default();
+        history.record(add("a"));
+        let undone = history.pop_undo().unwrap();
+        history.push_redo(undone);
+
+        history.record(add("b"));
+        assert!(history.pop_redo().is_none());
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LabelColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl LabelColor {
+    /// Colors handed out to new labels, in order.
+    pub const PALETTE: [LabelColor; 7] = [
+        LabelColor::Blue,
+        LabelColor::Green,
+        LabelColor::Magenta,
+        LabelColor::Yellow,
+        LabelColor::Cyan,
+        LabelColor::Red,
+        LabelColor::Gray,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LabelColor::Red => "red",
+            LabelColor::Green => "green",
+            LabelColor::Yellow => "yellow",
+            LabelColor::Blue => "blue",
+            LabelColor::Magenta => "magenta",
+            LabelColor::Cyan => "cyan",
+            LabelColor::Gray => "gray",
+        }
+    }
+
+    /// The palette color after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::PALETTE.iter().position(|color| *color == self).unwrap_or(0);
+        Self::PALETTE[(index + 1) % Self::PALETTE.len()]
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::PALETTE
+            .into_iter()
+            .find(|color| color.as_str() == value)
+    }
+}
+
+/// A board-level tag such as `bug` or `blocked`; tasks refer to labels by id.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Label {
+    pub id: Uuid,
+    pub name: String,
+    pub color: LabelColor,
+}
+
+impl Label {
+    pub fn new(name: String, color: LabelColor) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            color,
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use uuid::Uuid;
+
+use super::{
+    board::Board,
+    event::{EventKind, TaskEvent},
+};
+
+/// How many weeks of throughput the metrics screen shows.
+const THROUGHPUT_WEEKS: i64 = 8;
+
+/// Average time tasks spent in one column per visit.
+#[derive(Clone, Debug)]
+pub struct ColumnTime {
+    pub title: String,
+    pub average: Duration,
+    pub visits: usize,
+}
+
+/// Flow metrics derived from the task activity log. The first column is the
+/// backlog: work on a task starts when it first enters any later column, or
+/// when it is created in one. A task counts as completed when it last
+/// entered the done (last) column and stayed there.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    pub completed: usize,
+    /// Average time from creation to done.
+    pub lead_time: Option<Duration>,
+    /// Average time from the start of work to done.
+    pub cycle_time: Option<Duration>,
+    /// Completed tasks per week (starting Monday), oldest first.
+    pub weekly_throughput: Vec<(NaiveDate, usize)>,
+    /// Per column, in board order; the done column is left out.
+    pub column_times: Vec<ColumnTime>,
+}
+
+#[derive(Default)]
+struct TaskTimeline {
+    created_at: Option<DateTime<Utc>>,
+    /// When the task first left the backlog.
+    started_at: Option<DateTime<Utc>>,
+    done_at: Option<DateTime<Utc>>,
+    /// Column the task is in and since when.
+    current: Option<(Uuid, DateTime<Utc>)>,
+    /// Archiving or deleting stops the clock for the current column.
+    hidden_at: Option<DateTime<Utc>>,
+}
+
+/// Computes metrics from a board's events, which must be ordered oldest
+/// first. Tasks created before the log existed fall back to the board's
+/// `created_at` when they are still on it.
+pub fn compute(board: &Board, events: &[TaskEvent], now: DateTime<Utc>) -> Metrics {
+    let Some(done_column_id) = board.done_column_id() else {
+        return Metrics::default();
+    };
+    let first_column_id = board.columns.first().map(|column| column.id);
+
+    let mut timelines: HashMap<Uuid, TaskTimeline> = HashMap::new();
+    let mut column_totals: HashMap<Uuid, (Duration, usize)> = HashMap::new();
+    let mut add_visit = |column_id: Uuid, duration: Duration| {
+        let total = column_totals.entry(column_id).or_insert((Duration::zero(), 0));
+        total.0 += duration;
+        total.1 += 1;
+    };
+
+    for event in events {
+        let timeline = timelines.entry(event.task_id).or_default();
+        match &event.kind {
+            EventKind::Created => timeline.created_at = Some(event.at),
+            EventKind::Moved { from, to } => {
+                let entered = match timeline.current {
+                    Some((column_id, since)) if column_id == *from => since,
+                    _ => timeline
+                        .created_at
+                        .or_else(|| board.get_task(event.task_id).map(|task| task.created_at))
+                        .unwrap_or(event.at),
+                };
+                if *from != done_column_id {
+                    add_visit(*from, event.at - entered);
+                }
+                if timeline.started_at.is_none() {
+                    // A task first seen moving out of a later column was
+                    // created there, so work started when it entered it.
+                    timeline.started_at = Some(if Some(*from) == first_column_id { event.at } else { entered });
+                }
+                timeline.done_at = (*to == done_column_id).then_some(event.at);
+                timeline.current = Some((*to, event.at));
+            }
+            EventKind::Archived | EventKind::Deleted => timeline.hidden_at = Some(event.at),
+            EventKind::Restored => timeline.hidden_at = None,
+            EventKind::Changed { .. } => {}
+        }
+    }
+
+    // Time spent in the current column so far.
+    for timeline in timelines.values() {
+        if let Some((column_id, since)) = timeline.current
+            && column_id != done_column_id
+        {
+            add_visit(column_id, timeline.hidden_at.unwrap_or(now) - since);
+        }
+    }
+
+    let mut lead_times = Vec::new();
+    let mut cycle_times = Vec::new();
+    let mut done_dates = Vec::new();
+    for (task_id, timeline) in &timelines {
+        let Some(done_at) = timeline.done_at else {
+            continue;
+        };
+        done_dates.push(done_at.with_timezone(&Local).date_naive());
+
+        let created_at = timeline
+            .created_at
+            .or_else(|| board.get_task(*task_id).map(|task| task.created_at));
+        if let Some(created_at) = created_at {
+            lead_times.push(done_at - created_at);
+        }
+        if let Some(started_at) = timeline.started_at {
+            cycle_times.push(done_at - started_at);
+        }
+    }
+
+    let this_week = week_start(now.with_timezone(&Local).date_naive());
+    let weekly_throughput = (0..THROUGHPUT_WEEKS)
+        .rev()
+        .map(|weeks_ago| {
+            let start = this_week - Duration::weeks(weeks_ago);
+            let count = done_dates.iter().filter(|date| week_start(**date) == start).count();
+            (start, count)
+        })
+        .collect();
+
+    let column_times = board
+        .columns
+        .iter()
+        .filter(|column| column.id != done_column_id)
+        .filter_map(|column| {
+            let (total, visits) = column_totals.get(&column.id)?;
+            Some(ColumnTime {
+                title: column.title.clone(),
+                average: *total / *visits as i32,
+                visits: *visits,
+            })
+        })
+        .collect();
+
+    Metrics {
+        completed: done_dates.len(),
+        lead_time: average(&lead_times),
+        cycle_time: average(&cycle_times),
+        weekly_throughput,
+        column_times,
+    }
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn average(durations: &[Duration]) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    let total: Duration = durations.iter().copied().sum();
+    Some(total / durations.len() as i32)
+}
+
+/// Compact duration such as `3.5d`, `4.0h` or `12m`.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 24 * 60 {
+        format!("{:.1}h", minutes as f64 / 60.0)
+    } else {
+        format!("{:.1}d", minutes as f64 / (24.0 * 60.0))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Wednesday, so a few hours either way stay in the same local week.
+    fn now() -> DateTime<Utc> {
+        "2026-10-14T12:00:00Z".parse().unwrap()
+    }
+
+    fn hours_ago(hours: i64) -> DateTime<Utc> {
+        now() - Duration::hours(hours)
+    }
+
+    fn event(task_id: Uuid, hours: i64, kind: EventKind) -> TaskEvent {
+        TaskEvent { task_id, at: hours_ago(hours), kind }
+    }
+
+    fn moved(task_id: Uuid, hours: i64, from: usize, to: usize, board: &Board) -> TaskEvent {
+        let (from, to) = (board.columns[from].id, board.columns[to].id);
+        event(task_id, hours, EventKind::Moved { from, to })
+    }
+
+    #[test]
+    fn lead_and_cycle_time_of_a_task_through_every_column() {
+        let board = Board::new("Test".to_string());
+        let task = Uuid::new_v4();
+        let events = [
+            event(task, 10, EventKind::Created),
+            moved(task, 8, 0, 1, &board),
+            moved(task, 2, 1, 2, &board),
+        ];
+
+        let metrics = compute(&board, &events, now());
+        assert_eq!(metrics.completed, 1);
+        assert_eq!(metrics.lead_time, Some(Duration::hours(8)));
+        assert_eq!(metrics.cycle_time, Some(Duration::hours(6)));
+    }
+
+    #[test]
+    fn cycle_time_starts_on_entering_a_later_column_not_on_leaving_the_first() {
+        let mut board = Board::new("Test".to_string());
+        board.insert_column(1, "Ready".to_string());
+        let task = Uuid::new_v4();
+        let events = [
+            event(task, 10, EventKind::Created),
+            moved(task, 9, 0, 2, &board),
+            moved(task, 1, 2, 3, &board),
+        ];
+
+        assert_eq!(compute(&board, &events, now()).cycle_time, Some(Duration::hours(8)));
+    }
+
+    #[test]
+    fn tasks_created_in_a_later_column_start_when_created() {
+        let board = Board::new("Test".to_string());
+        let task = Uuid::new_v4();
+        let events = [event(task, 5, EventKind::Created), moved(task, 1, 1, 2, &board)];
+
+        let metrics = compute(&board, &events, now());
+        assert_eq!(metrics.lead_time, Some(Duration::hours(4)));
+        assert_eq!(metrics.cycle_time, Some(Duration::hours(4)));
+    }
+
+    #[test]
+    fn tasks_moved_back_out_of_done_are_not_completed() {
+        let board = Board::new("Test".to_string());
+        let task = Uuid::new_v4();
+        let events = [
+            event(task, 5, EventKind::Created),
+            moved(task, 4, 0, 2, &board),
+            moved(task, 3, 2, 1, &board),
+        ];
+
+        let metrics = compute(&board, &events, now());
+        assert_eq!(metrics.completed, 0);
+        assert_eq!(metrics.lead_time, None);
+        assert_eq!(metrics.cycle_time, None);
+    }
+
+    #[test]
+    fn throughput_counts_completions_per_week() {
+        let board = Board::new("Test".to_string());
+        let events: Vec<TaskEvent> = [1, 2, 24 * 7]
+            .into_iter()
+            .flat_map(|hours| {
+                let task = Uuid::new_v4();
+                [event(task, hours + 1, EventKind::Created), moved(task, hours, 0, 2, &board)]
+            })
+            .collect();
+
+        let throughput = compute(&board, &events, now()).weekly_throughput;
+        assert_eq!(throughput.len(), THROUGHPUT_WEEKS as usize);
+        let counts: Vec<usize> = throughput.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts[counts.len() - 2..], [1, 2]);
+        assert_eq!(counts.iter().sum::<usize>(), 3);
+        assert_eq!(throughput.last().unwrap().0.weekday(), chrono::Weekday::Mon);
+    }
+
+    #[test]
+    fn column_times_average_visits_and_count_the_current_one() {
+        let board = Board::new("Test".to_string());
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let events = [
+            event(first, 10, EventKind::Created),
+            moved(first, 6, 0, 1, &board),
+            moved(first, 4, 1, 2, &board),
+            event(second, 8, EventKind::Created),
+            moved(second, 6, 0, 1, &board),
+            // Archiving stops the clock for the column it is in
+            event(second, 2, EventKind::Archived),
+        ];
+
+        let times = compute(&board, &events, now()).column_times;
+        let times: Vec<(&str, Duration, usize)> =
+            times.iter().map(|time| (time.title.as_str(), time.average, time.visits)).collect();
+        assert_eq!(
+            times,
+            [("To Do", Duration::hours(3), 2), ("In Progress", Duration::hours(3), 2)]
+        );
+    }
+
+    #[test]
+    fn format_duration_picks_a_unit() {
+        assert_eq!(format_duration(Duration::minutes(12)), "12m");
+        assert_eq!(format_duration(Duration::minutes(4 * 60)), "4.0h");
+        assert_eq!(format_duration(Duration::hours(84)), "3.5d");
+    }
+}
@@ -1,5 +1,14 @@
 pub mod board;
+pub mod changes;
 pub mod column;
+pub mod dependency;
+pub mod due_date;
+pub mod event;
+pub mod filter;
+pub mod history;
+pub mod label;
+pub mod metrics;
+pub mod search;
 pub mod task;
 
 pub use board::Board;
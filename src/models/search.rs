@@ -0,0 +1,41 @@
+use std::ops::Range;
+
+/// Byte ranges of `text` whose lowercase form equals the lowercased `query`.
+/// Compared char by char, since lowercasing can change a char's length and
+/// offsets into the lowercased string don't map back onto `text`.
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut ranges = Vec::new();
+    if query.is_empty() {
+        return ranges;
+    }
+
+    let mut start = 0;
+    while start < chars.len() {
+        let mut matched = 0;
+        let mut end = start;
+        while matched < query.len() && end < chars.len() {
+            let lower: Vec<char> = chars[end].1.to_lowercase().collect();
+            if !query[matched..].starts_with(&lower) {
+                break;
+            }
+            matched += lower.len();
+            end += 1;
+        }
+
+        if matched == query.len() {
+            ranges.push(chars[start].0..chars.get(end).map_or(text.len(), |(i, _)| *i));
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// Whether `query` occurs in `text`, ignoring case. Agrees with
+/// `match_ranges`, so every hit has something to highlight.
+pub fn matches(text: &str, query: &str) -> bool {
+    !match_ranges(text, query).is_empty()
+}
@@ -1,21 +1,18 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+use super::due_date::{self, DueStatus};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Priority {
     Low,
+    #[default]
     Medium,
     High,
     Critical,
 }
 
-impl Default for Priority {
-    fn default() -> Self {
-        Priority::Medium
-    }
-}
-
 impl std::fmt::Display for Priority {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -27,6 +24,41 @@ impl std::fmt::Display for Priority {
     }
 }
 
+/// One step of a task's checklist.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub text: String,
+    pub done: bool,
+}
+
+impl ChecklistItem {
+    pub fn new(text: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            text,
+            done: false,
+        }
+    }
+}
+
+/// Why a task is off the board. Hidden tasks keep their column so they
+/// can be restored there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HiddenState {
+    Archived,
+    Deleted,
+}
+
+impl std::fmt::Display for HiddenState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HiddenState::Archived => write!(f, "archived"),
+            HiddenState::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Task {
     pub id: Uuid,
@@ -34,8 +66,22 @@ pub struct Task {
     pub description: Option<String>,
     pub due_date: Option<DateTime<Utc>>,
     pub priority: Priority,
+    /// Ids of board labels attached to this task.
+    #[serde(default)]
+    pub labels: Vec<Uuid>,
+    /// Ordered steps; the card shows how many are done.
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    /// Ids of tasks that have to be finished before this one.
+    #[serde(default)]
+    pub blocked_by: Vec<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Set when the task was moved to the trash.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl Task {
@@ -47,8 +93,13 @@ impl Task {
             description: None,
             due_date: None,
             priority: Priority::default(),
+            labels: Vec::new(),
+            checklist: Vec::new(),
+            blocked_by: Vec::new(),
             created_at: now,
             updated_at: now,
+            archived_at: None,
+            deleted_at: None,
         }
     }
 
@@ -59,12 +110,36 @@ impl Task {
     }
 
 
+    pub fn with_due_date(mut self, due_date: Option<DateTime<Utc>>) -> Self {
+        self.due_date = due_date;
+        self.updated_at = Utc::now();
+        self
+    }
+
     pub fn with_priority(mut self, priority: Priority) -> Self {
         self.priority = priority;
         self.updated_at = Utc::now();
         self
     }
 
+    pub fn with_labels(mut self, labels: Vec<Uuid>) -> Self {
+        self.labels = labels;
+        self.updated_at = Utc::now();
+        self
+    }
+
+    pub fn with_checklist(mut self, checklist: Vec<ChecklistItem>) -> Self {
+        self.checklist = checklist;
+        self.updated_at = Utc::now();
+        self
+    }
+
+    pub fn with_blockers(mut self, blocked_by: Vec<Uuid>) -> Self {
+        self.blocked_by = blocked_by;
+        self.updated_at = Utc::now();
+        self
+    }
+
     pub fn update_title(&mut self, title: String) {
         self.title = title;
         self.updated_at = Utc::now();
@@ -76,8 +151,74 @@ impl Task {
     }
 
 
+    pub fn update_due_date(&mut self, due_date: Option<DateTime<Utc>>) {
+        self.due_date = due_date;
+        self.updated_at = Utc::now();
+    }
+
     pub fn update_priority(&mut self, priority: Priority) {
         self.priority = priority;
         self.updated_at = Utc::now();
     }
-}
\ No newline at end of file
+
+    pub fn update_labels(&mut self, labels: Vec<Uuid>) {
+        self.labels = labels;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn update_checklist(&mut self, checklist: Vec<ChecklistItem>) {
+        self.checklist = checklist;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn update_blockers(&mut self, blocked_by: Vec<Uuid>) {
+        self.blocked_by = blocked_by;
+        self.updated_at = Utc::now();
+    }
+
+    /// Done and total checklist items, or `None` without a checklist.
+    pub fn checklist_progress(&self) -> Option<(usize, usize)> {
+        if self.checklist.is_empty() {
+            return None;
+        }
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        Some((done, self.checklist.len()))
+    }
+
+    pub fn is_checklist_complete(&self) -> bool {
+        self.checklist_progress().is_some_and(|(done, total)| done == total)
+    }
+
+    pub fn hidden_state(&self) -> Option<HiddenState> {
+        if self.deleted_at.is_some() {
+            Some(HiddenState::Deleted)
+        } else if self.archived_at.is_some() {
+            Some(HiddenState::Archived)
+        } else {
+            None
+        }
+    }
+
+    /// When the task was archived or deleted, whichever is set.
+    pub fn hidden_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at.or(self.archived_at)
+    }
+
+    pub fn hide(&mut self, state: HiddenState) {
+        let now = Utc::now();
+        match state {
+            HiddenState::Archived => self.archived_at = Some(now),
+            HiddenState::Deleted => self.deleted_at = Some(now),
+        }
+    }
+
+    pub fn unhide(&mut self) {
+        self.archived_at = None;
+        self.deleted_at = None;
+    }
+
+    pub fn due_status(&self, today: NaiveDate) -> Option<DueStatus> {
+        self.due_date
+            .map(|date| due_date::due_status(date.date_naive(), today))
+    }
+}
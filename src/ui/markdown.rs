@@ -0,0 +1,353 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+const HEADING: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+const CODE: Style = Style::new().fg(Color::Yellow);
+const LINK: Style = Style::new().fg(Color::Blue).add_modifier(Modifier::UNDERLINED);
+const URL: Style = Style::new().fg(Color::DarkGray);
+const MARKER: Style = Style::new().fg(Color::DarkGray);
+const CHECKED: Style = Style::new().fg(Color::Green);
+
+/// Renders a task description written in Markdown as styled text for the
+/// detail view. Single line breaks are kept, since descriptions are typed
+/// line by line in the popup, and code blocks keep their indentation.
+pub fn render(markdown: &str) -> Text<'static> {
+    let mut renderer = Renderer::default();
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH);
+    for event in parser {
+        renderer.event(event);
+    }
+    renderer.flush();
+    Text::from(renderer.lines)
+}
+
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<Line<'static>>,
+    spans: Vec<Span<'static>>,
+    /// Inline styles currently open, innermost last.
+    styles: Vec<Style>,
+    /// Next number of each open list, `None` for bullet lists.
+    lists: Vec<Option<u64>>,
+    /// Indentation each open list item or block quote adds to its lines.
+    indents: Vec<Span<'static>>,
+    /// Marker of a list item whose first line hasn't been started yet.
+    marker: Option<Span<'static>>,
+    /// Destinations of the links currently open.
+    links: Vec<String>,
+    /// Text seen inside the innermost open link, to tell whether the URL
+    /// needs printing.
+    link_text: String,
+    in_code_block: bool,
+    /// Whether a block ended and the next one needs a blank line first.
+    block_ended: bool,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.in_code_block => {
+                for line in text.lines() {
+                    self.spans.push(Span::styled(line.to_string(), CODE));
+                    self.new_line();
+                }
+            }
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => self.spans.push(Span::styled(code.to_string(), self.style().patch(CODE))),
+            Event::SoftBreak | Event::HardBreak => self.new_line(),
+            Event::Rule => {
+                self.start_block();
+                self.spans.push(Span::styled("─".repeat(20), MARKER));
+                self.new_line();
+                self.block_ended = true;
+            }
+            Event::TaskListMarker(checked) => {
+                let (marker, style) = if checked { ("[x] ", CHECKED) } else { ("[ ] ", MARKER) };
+                let marker = Span::styled(marker, style);
+                if self.marker.is_some() {
+                    // The checkbox takes the place of the bullet
+                    if let Some(indent) = self.indents.last_mut() {
+                        *indent = Span::raw(" ".repeat(marker.width()));
+                    }
+                    self.marker = Some(marker);
+                } else {
+                    self.spans.push(marker);
+                }
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.text(&html),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                let style = match level {
+                    HeadingLevel::H1 => HEADING.add_modifier(Modifier::UNDERLINED),
+                    HeadingLevel::H2 => HEADING,
+                    _ => Style::new().add_modifier(Modifier::BOLD),
+                };
+                self.styles.push(style);
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.indents.push(Span::styled("│ ", MARKER));
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                if let CodeBlockKind::Fenced(language) = kind
+                    && !language.is_empty()
+                {
+                    self.spans.push(Span::styled(language.to_string(), MARKER));
+                    self.new_line();
+                }
+                self.in_code_block = true;
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    self.new_line();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.new_line();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                let marker = Span::styled(marker, MARKER);
+                self.indents.push(Span::raw(" ".repeat(marker.width())));
+                self.marker = Some(marker);
+            }
+            Tag::Emphasis => self.styles.push(self.style().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.styles.push(self.style().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.styles.push(self.style().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.styles.push(self.style().patch(LINK));
+                self.links.push(dest_url.to_string());
+                self.link_text.clear();
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.new_line();
+                self.block_ended = true;
+            }
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.new_line();
+                self.block_ended = true;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.new_line();
+                self.indents.pop();
+                self.block_ended = true;
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.new_line();
+                self.block_ended = true;
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                self.new_line();
+                self.block_ended = self.lists.is_empty();
+            }
+            TagEnd::Item => {
+                self.new_line();
+                self.indents.pop();
+                self.marker = None;
+                self.block_ended = false;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                if let Some(url) = self.links.pop()
+                    && !url.is_empty()
+                    && url != self.link_text
+                {
+                    self.spans.push(Span::styled(format!(" ({})", url), URL));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn text(&mut self, text: &str) {
+        if !self.links.is_empty() {
+            self.link_text.push_str(text);
+        }
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.new_line();
+            }
+            if !line.is_empty() {
+                self.spans.push(Span::styled(line.to_string(), self.style()));
+            }
+        }
+    }
+
+    /// Separates a new block from the previous one with a blank line,
+    /// except at the top of a list item.
+    fn start_block(&mut self) {
+        self.new_line();
+        if self.block_ended && self.marker.is_none() && !self.lines.is_empty() {
+            self.lines.push(Line::default());
+        }
+        self.block_ended = false;
+    }
+
+    /// Finishes the current line, prefixed with the indentation of the
+    /// enclosing lists and quotes. Does nothing if the line is empty.
+    fn new_line(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+        let mut spans = self.indents.clone();
+        if let Some(marker) = self.marker.take() {
+            spans.pop();
+            spans.push(marker);
+        }
+        spans.append(&mut self.spans);
+        self.lines.push(Line::from(spans));
+    }
+
+    fn flush(&mut self) {
+        self.new_line();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    fn lines(markdown: &str) -> Vec<String> {
+        render(markdown).lines.iter().map(line_text).collect()
+    }
+
+    fn span<'a>(text: &'a Text, content: &str) -> &'a Span<'a> {
+        text.lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .find(|span| span.content == content)
+            .unwrap_or_else(|| panic!("no span {:?}", content))
+    }
+
+    #[test]
+    fn plain_lines_are_kept() {
+        assert_eq!(lines("first\nsecond"), ["first", "second"]);
+    }
+
+    #[test]
+    fn paragraphs_are_separated_by_a_blank_line() {
+        assert_eq!(lines("one\n\ntwo"), ["one", "", "two"]);
+    }
+
+    #[test]
+    fn headings_are_styled_by_level() {
+        let text = render("# Title\n## Section\n### Detail");
+        assert_eq!(span(&text, "Title").style, HEADING.add_modifier(Modifier::UNDERLINED));
+        assert_eq!(span(&text, "Section").style, HEADING);
+        assert_eq!(span(&text, "Detail").style, Style::new().add_modifier(Modifier::BOLD));
+    }
+
+    #[test]
+    fn emphasis_nests() {
+        let text = render("*it **both***");
+        assert_eq!(span(&text, "it ").style, Style::new().add_modifier(Modifier::ITALIC));
+        assert_eq!(
+            span(&text, "both").style,
+            Style::new().add_modifier(Modifier::ITALIC | Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn inline_code_is_highlighted() {
+        let text = render("run `cargo test` now");
+        assert_eq!(line_text(&text.lines[0]), "run cargo test now");
+        assert_eq!(span(&text, "cargo test").style, CODE);
+        assert_eq!(span(&text, "run ").style, Style::new());
+    }
+
+    #[test]
+    fn fenced_code_keeps_indentation_and_markup() {
+        let text = render("```rust\nfn main() {\n    *not emphasis*\n}\n```");
+        assert_eq!(
+            text.lines.iter().map(line_text).collect::<Vec<_>>(),
+            ["rust", "fn main() {", "    *not emphasis*", "}"]
+        );
+        assert_eq!(span(&text, "rust").style, MARKER);
+        assert_eq!(span(&text, "    *not emphasis*").style, CODE);
+    }
+
+    #[test]
+    fn bullet_lists_nest() {
+        assert_eq!(
+            lines("- one\n  - inner\n- two"),
+            ["• one", "  • inner", "• two"]
+        );
+    }
+
+    #[test]
+    fn numbered_lists_count_from_their_start() {
+        assert_eq!(lines("3. three\n4. four"), ["3. three", "4. four"]);
+    }
+
+    #[test]
+    fn wrapped_item_lines_align_with_the_text() {
+        assert_eq!(lines("1. first\n   continued"), ["1. first", "   continued"]);
+    }
+
+    #[test]
+    fn task_list_checkboxes_replace_the_bullet() {
+        let text = render("- [x] done\n- [ ] todo");
+        assert_eq!(
+            text.lines.iter().map(line_text).collect::<Vec<_>>(),
+            ["[x] done", "[ ] todo"]
+        );
+        assert_eq!(span(&text, "[x] ").style, CHECKED);
+        assert_eq!(span(&text, "[ ] ").style, MARKER);
+    }
+
+    #[test]
+    fn links_show_their_destination() {
+        let text = render("see [docs](https://example.com) and <https://example.org>");
+        assert_eq!(
+            line_text(&text.lines[0]),
+            "see docs (https://example.com) and https://example.org"
+        );
+        assert_eq!(span(&text, "docs").style, LINK);
+        assert_eq!(span(&text, " (https://example.com)").style, URL);
+    }
+
+    #[test]
+    fn list_after_paragraph_is_separated() {
+        assert_eq!(lines("Steps:\n\n- a\n- b\n\nDone"), ["Steps:", "", "• a", "• b", "", "Done"]);
+    }
+}
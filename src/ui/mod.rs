@@ -0,0 +1,1311 @@
+pub mod markdown;
+pub mod text_editor;
+
+use chrono::{DateTime, Local, Utc};
+use unicode_width::UnicodeWidthStr;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::{App, EditField, InputMode},
+    models::{
+        Priority,
+        due_date::{DueStatus, describe_due_date},
+        event::{EventKind, TaskEvent},
+        filter::DueFilter,
+        label::{Label, LabelColor},
+        metrics::format_duration,
+        search::match_ranges,
+        task::HiddenState,
+    },
+};
+
+use text_editor::TextEditor;
+
+/// Narrowest a board column gets before columns are paged horizontally.
+const MIN_COLUMN_WIDTH: u16 = 24;
+
+/// Draws the whole screen. `app` is mutable only so views with scroll state
+/// can clamp it to what was actually rendered.
+pub fn ui(f: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),     // Main content
+            Constraint::Length(3),  // Status bar
+        ])
+        .split(f.area());
+
+    // Header
+    let save_indicator = if app.save_error.is_some() {
+        Span::styled("  ✗ save failed", Style::default().fg(Color::Red))
+    } else if app.board.is_dirty() {
+        Span::styled("  ● unsaved", Style::default().fg(Color::Yellow))
+    } else {
+        Span::styled("  ✓ saved", Style::default().fg(Color::Green))
+    };
+    let mut header_spans = vec![
+        Span::raw(format!("Kanban TUI - {}", app.board.title)),
+        save_indicator,
+    ];
+    if app.is_filter_active() {
+        header_spans.push(Span::styled(
+            format!("  ⧩ {}", app.filter_description()),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    let header = Paragraph::new(Line::from(header_spans))
+        .style(Style::default().fg(Color::Cyan))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default()),
+        );
+    f.render_widget(header, chunks[0]);
+
+    // Main kanban board, or the selected task's details in its place
+    if matches!(app.input_mode, InputMode::TaskDetail) {
+        render_task_detail(f, chunks[1], app);
+    } else {
+        render_board(f, chunks[1], app);
+    }
+
+    // Status bar
+    let status_text = match app.input_mode {
+        // n/N step through matches while a search is kept, so adding a task needs Esc first
+        InputMode::Normal if app.is_search_active() => format!(
+            "{}Status: {} | Search \"{}\": n/N=next/prev match, Esc=clear search (n=new task again), Enter=edit, i=details, q=quit",
+            due_summary_text(app),
+            app.status_message,
+            app.search_query
+        ),
+        InputMode::Normal => format!(
+            "{}Status: {} | Controls: hjkl/arrows=move, n=new task, Enter=edit, e=$EDITOR, i=details, d=delete, a/A=archive task/column, t=trash, s=metrics, u/^r=undo/redo, m/M=move task, f=filter, v=views, b=boards, q=quit",
+            due_summary_text(app),
+            app.status_message
+        ),
+        InputMode::AddingTask | InputMode::Editing => format!(
+            "Status: {} | Enter/Ctrl-S: confirm (Enter adds a line in the description), Ctrl-O: open in $EDITOR, Esc: cancel, Tab/↓: next field, ↑: prev field",
+            app.status_message
+        ),
+        InputMode::MovingTask => "Moving task - ←/→: select target column, Enter: confirm, Esc: cancel".to_string(),
+        InputMode::BoardPicker => format!(
+            "Status: {} | Boards: j/k=select, Enter=open, n=new, r=rename, d=delete, Esc=close",
+            app.status_message
+        ),
+        InputMode::NamingBoard => "Board name - Enter: confirm, Esc: cancel".to_string(),
+        InputMode::ManagingColumns => format!(
+            "Status: {} | Columns: h/l=select, H/L=reorder, a=add, r=rename, d=delete, w=WIP limit, p=warn/block, Esc=done",
+            app.status_message
+        ),
+        InputMode::NamingColumn => "Column name - Enter: confirm, Esc: cancel".to_string(),
+        InputMode::SettingWipLimit => "WIP limit - digits, empty for none, Enter: confirm, Esc: cancel".to_string(),
+        InputMode::Searching => format!(
+            "/{}▏ ({} matches) - Enter: keep search (n/N: next/prev), Esc: cancel",
+            app.search_query,
+            app.search_hits().len()
+        ),
+        InputMode::DeletingColumn => format!(
+            "Status: {} | m: move tasks to neighbouring column, x: move them to the trash, Esc: cancel",
+            app.status_message
+        ),
+        InputMode::Filtering if app.filter_text_editing => {
+            "Filter text - type to filter, Enter/Esc: done".to_string()
+        }
+        InputMode::Filtering => format!(
+            "Status: {} | c/h/m/l=priority, d=due, e=no description, t=label, C=label color, R=rename label, T=delete unused label, /=text, x=clear, s=save view, v=next view, D=delete view, Esc=close",
+            app.status_message
+        ),
+        InputMode::NamingView => "View name - Enter: save, Esc: cancel".to_string(),
+        InputMode::RenamingLabel => format!("Status: {} | Label name - Enter: save, Esc: cancel", app.status_message),
+        InputMode::Archive => format!(
+            "Status: {} | Archive: j/k=select, r/Enter=restore, P=purge for good, Esc=close",
+            app.status_message
+        ),
+        InputMode::TaskDetail => "Task details - j/k, PgUp/PgDn, g/G: scroll, Enter/e: edit, Esc: close".to_string(),
+        InputMode::Metrics => "Metrics - Esc: close".to_string(),
+    };
+
+    let status_bar = Paragraph::new(status_text)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Left)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default()),
+        );
+    f.render_widget(status_bar, chunks[2]);
+
+    // Render input popup if needed
+    match app.input_mode {
+        InputMode::AddingTask | InputMode::Editing => render_input_popup(f, app),
+        InputMode::BoardPicker => render_board_picker(f, app),
+        InputMode::NamingBoard => {
+            render_board_picker(f, app);
+            let title = if app.renaming_board_id.is_some() { "Rename Board" } else { "New Board" };
+            render_name_popup(f, title, &app.board_name_input);
+        }
+        InputMode::NamingColumn => {
+            let title = if app.renaming_column_id.is_some() { "Rename Column" } else { "New Column" };
+            render_name_popup(f, title, &app.column_name_input);
+        }
+        InputMode::SettingWipLimit => render_name_popup(f, "WIP Limit", &app.wip_limit_input),
+        InputMode::DeletingColumn => render_delete_column_popup(f, app),
+        InputMode::Filtering => render_filter_panel(f, app),
+        InputMode::Archive => render_archive(f, app),
+        InputMode::Metrics => render_metrics(f, app),
+        InputMode::NamingView => {
+            render_filter_panel(f, app);
+            render_name_popup(f, "Save View", &app.view_name_input);
+        }
+        InputMode::RenamingLabel => {
+            render_filter_panel(f, app);
+            render_name_popup(f, "Rename Label", &app.label_name_input);
+        }
+        _ => {}
+    }
+}
+
+/// Status bar prefix such as "3 overdue, 1 due today | ", empty when nothing is due.
+fn due_summary_text(app: &App) -> String {
+    let summary = &app.due_summary;
+    let parts: Vec<String> = [
+        (summary.overdue, "overdue"),
+        (summary.today, "due today"),
+        (summary.this_week, "due this week"),
+    ]
+    .iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, label)| format!("{} {}", count, label))
+    .collect();
+
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("⚠ {} | ", parts.join(", "))
+    }
+}
+
+fn render_board(f: &mut Frame, area: Rect, app: &mut App) {
+    // Columns narrower than this are paged instead, keeping the column with
+    // the selection (or the move target) on screen.
+    let column_count = app.board.columns.len();
+    let per_page = (area.width / MIN_COLUMN_WIDTH).clamp(1, column_count.max(1) as u16) as usize;
+    let focus = if matches!(app.input_mode, InputMode::MovingTask) {
+        app.target_column
+    } else {
+        app.selected_column
+    };
+    let first = focus - focus % per_page;
+    let shown = first..(first + per_page).min(column_count);
+
+    let column_width = area.width / per_page as u16;
+    let columns_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Length(column_width); per_page])
+        .split(area);
+    // Kept for mouse hit-testing; columns on other pages get an empty area
+    app.column_areas = (0..column_count)
+        .map(|col_idx| {
+            if shown.contains(&col_idx) {
+                columns_layout[col_idx - first]
+            } else {
+                Rect::default()
+            }
+        })
+        .collect();
+
+    let list_height = area.height.saturating_sub(2) as usize;
+    for col_idx in shown.clone() {
+        let column_id = app.board.columns[col_idx].id;
+        let len = app.visible_tasks(col_idx).len();
+        let selected = (col_idx == app.selected_column).then_some(app.selected_task);
+        let state = app.column_states.entry(column_id).or_default();
+        scroll_into_view(state, selected, len, list_height);
+    }
+    let app = &*app;
+
+    let done_column_id = app.board.done_column_id();
+
+    for (col_idx, column) in app.board.columns.iter().enumerate().skip(first).take(per_page) {
+        let is_selected_column = col_idx == app.selected_column;
+        let is_done_column = Some(column.id) == done_column_id;
+        let is_target_column = matches!(app.input_mode, InputMode::MovingTask) && col_idx == app.target_column;
+        
+        let managing_columns = matches!(
+            app.input_mode,
+            InputMode::ManagingColumns
+                | InputMode::NamingColumn
+                | InputMode::SettingWipLimit
+                | InputMode::DeletingColumn
+        );
+
+        let border_style = if is_target_column {
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+        } else if is_selected_column && managing_columns {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else if is_selected_column {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else if column.is_over_limit() {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+
+        // "4/3" against the WIP limit; with a filter the shown count comes first.
+        let visible_tasks = app.visible_tasks(col_idx);
+        let count = match (app.is_filter_active(), column.wip_limit) {
+            (false, None) => column.tasks.len().to_string(),
+            (false, Some(limit)) => format!("{}/{}", column.tasks.len(), limit),
+            (true, None) => format!("{}/{}", visible_tasks.len(), column.tasks.len()),
+            (true, Some(limit)) => format!("{} shown, {}/{}", visible_tasks.len(), column.tasks.len(), limit),
+        };
+        let title_style = if column.is_over_limit() {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+
+        let mut state = app.column_states.get(&column.id).cloned().unwrap_or_default();
+        let indicator = Style::default().fg(Color::Yellow);
+        let mut block = Block::default().borders(Borders::ALL).style(border_style);
+        if col_idx == first && first > 0 {
+            block = block.title(Span::styled(format!("◀ {}", first), indicator));
+        }
+        block = block.title(Span::styled(format!("{} ({})", column.title, count), title_style));
+        if col_idx + 1 == shown.end && shown.end < column_count {
+            let hidden = column_count - shown.end;
+            block = block.title(Line::from(Span::styled(format!("{} ▶", hidden), indicator)).right_aligned());
+        }
+        // Counts of cards scrolled out of view above and below
+        let above = state.offset();
+        let below = visible_tasks.len().saturating_sub(state.offset() + list_height);
+        if above > 0 {
+            block = block.title_bottom(Line::from(Span::styled(format!("▲ {}", above), indicator)));
+        }
+        if below > 0 {
+            block = block.title_bottom(Line::from(Span::styled(format!("▼ {}", below), indicator)).right_aligned());
+        }
+
+        let tasks: Vec<ListItem> = visible_tasks
+            .into_iter()
+            .enumerate()
+            .map(|(task_idx, task)| {
+                let is_selected = is_selected_column && task_idx == app.selected_task;
+                let is_being_moved = app.moving_task_id.is_some() && app.moving_task_id == Some(task.id);
+                let is_blocked = !is_done_column && app.board.is_blocked(task);
+                
+                let priority_indicator = match task.priority {
+                    Priority::Critical => "🔴",
+                    Priority::High => "🟡",
+                    Priority::Medium => "🔵",
+                    Priority::Low => "🟢",
+                };
+
+                let due_date_str = task
+                    .due_date
+                    .map(|date| format!(" [{}]", date.format("%m/%d")))
+                    .unwrap_or_default();
+
+                // Cards in the done column are finished, so their dates are no longer urgent.
+                let due_style = match task.due_status(app.today) {
+                    _ if is_done_column => Style::default(),
+                    Some(DueStatus::Overdue) => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    Some(DueStatus::Today) => Style::default().fg(Color::LightRed),
+                    Some(DueStatus::ThisWeek) => Style::default().fg(Color::LightYellow),
+                    Some(DueStatus::Later) | None => Style::default(),
+                };
+
+                let style = if is_being_moved {
+                    Style::default()
+                        .bg(Color::Yellow)
+                        .fg(Color::Black)
+                        .add_modifier(Modifier::BOLD)
+                } else if is_selected {
+                    Style::default()
+                        .bg(Color::Blue)
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD)
+                } else if is_blocked {
+                    Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)
+                } else {
+                    Style::default()
+                };
+
+                let mut spans = vec![Span::styled(format!("{} ", priority_indicator), style)];
+                if is_blocked {
+                    spans.push(Span::styled("⊘ ", style.patch(Style::default().fg(Color::Magenta))));
+                }
+                spans.extend(highlight_matches(&task.title, &app.search_query, style));
+                let title_hit = task.title.to_lowercase().contains(&app.search_query.to_lowercase());
+                if !title_hit && app.task_matches_search(task) {
+                    // Matched on the description only
+                    spans.push(Span::styled(" …", style.patch(search_match_style())));
+                }
+                spans.push(Span::styled(due_date_str, style.patch(due_style)));
+                if let Some((done, total)) = task.checklist_progress() {
+                    let progress_style = if done == total {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::Gray)
+                    };
+                    spans.push(Span::styled(format!(" [{}/{}]", done, total), style.patch(progress_style)));
+                }
+                for label in task.labels.iter().filter_map(|id| app.board.get_label(*id)) {
+                    spans.push(Span::styled(" ", style));
+                    spans.push(label_chip(label));
+                }
+
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(tasks).block(block);
+        f.render_stateful_widget(list, columns_layout[col_idx - first], &mut state);
+    }
+}
+
+/// Scrolls a column's list the least needed to show the `selected` card,
+/// without leaving empty rows below the last one.
+fn scroll_into_view(state: &mut ListState, selected: Option<usize>, len: usize, height: usize) {
+    let selected = selected.filter(|selected| *selected < len);
+    let mut offset = state.offset().min(len.saturating_sub(height));
+    if let Some(selected) = selected {
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + height {
+            offset = selected + 1 - height.max(1);
+        }
+    }
+    *state.offset_mut() = offset;
+    state.select(selected);
+}
+
+fn search_match_style() -> Style {
+    Style::default().bg(Color::Yellow).fg(Color::Black)
+}
+
+/// Splits `text` into spans, highlighting every case-insensitive occurrence
+/// of `query`.
+fn highlight_matches<'a>(text: &'a str, query: &str, base: Style) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for range in match_ranges(text, query) {
+        if range.start > last {
+            spans.push(Span::styled(&text[last..range.start], base));
+        }
+        last = range.end;
+        spans.push(Span::styled(&text[range], base.patch(search_match_style())));
+    }
+    if last < text.len() || spans.is_empty() {
+        spans.push(Span::styled(&text[last..], base));
+    }
+    spans
+}
+
+fn label_color(color: LabelColor) -> Color {
+    match color {
+        LabelColor::Red => Color::Red,
+        LabelColor::Green => Color::Green,
+        LabelColor::Yellow => Color::Yellow,
+        LabelColor::Blue => Color::Blue,
+        LabelColor::Magenta => Color::Magenta,
+        LabelColor::Cyan => Color::Cyan,
+        LabelColor::Gray => Color::Gray,
+    }
+}
+
+fn label_chip(label: &Label) -> Span<'_> {
+    Span::styled(
+        format!(" {} ", label.name),
+        Style::default().bg(label_color(label.color)).fg(Color::Black),
+    )
+}
+
+fn render_input_popup(f: &mut Frame, app: &App) {
+    let popup_area = centered_rect(60, 90, f.area());
+    f.render_widget(Clear, popup_area);
+
+    let title = match app.input_mode {
+        InputMode::AddingTask => "Add New Task",
+        InputMode::Editing => "Edit Task",
+        _ => "Input",
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let input_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title input
+            Constraint::Length(5), // Description input
+            Constraint::Length(3), // Due date input
+            Constraint::Length(1), // Due date preview
+            Constraint::Length(3), // Priority selection
+            Constraint::Length(3), // Labels input
+            Constraint::Length(1), // Known labels
+            Constraint::Min(4),     // Checklist
+            Constraint::Min(5),     // Blockers
+        ])
+        .margin(1)
+        .split(inner);
+
+    // Title input
+    let title_selected = matches!(app.edit_state.selected_field, EditField::Title);
+    let title_style = if title_selected {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let title_border_style = if title_selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
+    app.edit_state.title.render(
+        f,
+        input_chunks[0],
+        Block::default()
+            .title(if title_selected { "Title [SELECTED]" } else { "Title" })
+            .borders(Borders::ALL)
+            .style(title_border_style),
+        title_style,
+        title_selected,
+    );
+
+    // Description input
+    let desc_selected = matches!(app.edit_state.selected_field, EditField::Description);
+    let desc_style = if desc_selected {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let desc_border_style = if desc_selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
+    app.edit_state.description.render(
+        f,
+        input_chunks[1],
+        Block::default()
+            .title(if desc_selected { "Description [SELECTED]" } else { "Description" })
+            .borders(Borders::ALL)
+            .style(desc_border_style),
+        desc_style,
+        desc_selected,
+    );
+
+    // Due date input
+    let due_selected = matches!(app.edit_state.selected_field, EditField::DueDate);
+    let due_style = if due_selected {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let due_border_style = if due_selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
+    let due_title = "Due Date (2026-11-03, tomorrow, next fri, +3d; Ctrl-U to clear)";
+    app.edit_state.due_date.render(
+        f,
+        input_chunks[2],
+        Block::default()
+            .title(if due_selected { format!("{} [SELECTED]", due_title) } else { due_title.to_string() })
+            .borders(Borders::ALL)
+            .style(due_border_style),
+        due_style,
+        due_selected,
+    );
+
+    // Live preview of how the due date input is understood
+    let today = Local::now().date_naive();
+    let due_preview = match app.edit_state.parsed_due_date() {
+        Ok(Some(date)) => Span::styled(
+            format!(" → {}", describe_due_date(date, today)),
+            Style::default().fg(Color::Green),
+        ),
+        Ok(None) => Span::styled(" → No due date", Style::default().fg(Color::DarkGray)),
+        Err(e) => Span::styled(format!(" ✗ {}", e), Style::default().fg(Color::Red)),
+    };
+    f.render_widget(Paragraph::new(Line::from(due_preview)), input_chunks[3]);
+
+    // Priority selection
+    let priority_selected = matches!(app.edit_state.selected_field, EditField::Priority);
+    let priority_text = format!("Priority: {}", app.edit_state.priority);
+    let priority_border_style = if priority_selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
+    let priority_display = Paragraph::new(priority_text)
+        .style(Style::default())
+        .block(
+            Block::default()
+                .title(if priority_selected { "Priority (use +/- to change) [SELECTED]" } else { "Priority (use +/- to change)" })
+                .borders(Borders::ALL)
+                .style(priority_border_style),
+        );
+    f.render_widget(priority_display, input_chunks[4]);
+
+    // Labels input
+    let labels_selected = matches!(app.edit_state.selected_field, EditField::Labels);
+    let labels_style = if labels_selected {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let labels_border_style = if labels_selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
+    app.edit_state.labels.render(
+        f,
+        input_chunks[5],
+        Block::default()
+            .title(if labels_selected { "Labels (comma separated) [SELECTED]" } else { "Labels (comma separated)" })
+            .borders(Borders::ALL)
+            .style(labels_border_style),
+        labels_style,
+        labels_selected,
+    );
+
+    // Labels already on the board; names typed that aren't listed are created on save
+    let entered = app.edit_state.label_names();
+    let mut known = vec![Span::styled(" Known: ", Style::default().fg(Color::DarkGray))];
+    for label in &app.board.labels {
+        let chip = label_chip(label);
+        if entered.iter().any(|name| name.eq_ignore_ascii_case(&label.name)) {
+            known.push(chip.patch_style(Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)));
+        } else {
+            known.push(chip);
+        }
+        known.push(Span::raw(" "));
+    }
+    let new_labels: Vec<&str> = entered
+        .iter()
+        .filter(|name| app.board.find_label(name).is_none())
+        .copied()
+        .collect();
+    if !new_labels.is_empty() {
+        known.push(Span::styled(
+            format!(" new: {}", new_labels.join(", ")),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(known)), input_chunks[6]);
+
+    // Checklist items followed by the input for a new one
+    let checklist_selected = matches!(app.edit_state.selected_field, EditField::Checklist);
+    let checklist_border_style = if checklist_selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
+    let checklist_lines: Vec<Line> = app
+        .edit_state
+        .checklist
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let mark = if item.done { "[x]" } else { "[ ]" };
+            let style = if checklist_selected && idx == app.edit_state.selected_item {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            } else if item.done {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!("{} {}", mark, item.text), style))
+        })
+        .collect();
+    let checklist_title = "Checklist (Enter: add, Alt-↑/↓: select, Ctrl-X: toggle, Ctrl-D: remove)";
+    let checklist_block = Block::default()
+        .title(if checklist_selected { format!("{} [SELECTED]", checklist_title) } else { checklist_title.to_string() })
+        .borders(Borders::ALL)
+        .style(checklist_border_style);
+    let checklist_inner = checklist_block.inner(input_chunks[7]);
+    f.render_widget(checklist_block, input_chunks[7]);
+    let [items_area, new_item_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(checklist_inner);
+    let items_scroll = app.edit_state.selected_item.saturating_sub(items_area.height.saturating_sub(1) as usize);
+    f.render_widget(Paragraph::new(checklist_lines).scroll((items_scroll as u16, 0)), items_area);
+    render_inline_input(f, new_item_area, "+ ", &app.edit_state.checklist_input, checklist_selected);
+
+    // Current blockers, then the title search and its matches
+    let blockers_selected = matches!(app.edit_state.selected_field, EditField::Blockers);
+    let blockers_border_style = if blockers_selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
+    let searching = !app.edit_state.blocker_query.is_empty();
+    let highlight = Style::default().bg(Color::Blue).fg(Color::White);
+    let mut blocker_spans: Vec<Span> = Vec::new();
+    for (idx, blocker_id) in app.edit_state.blockers.iter().enumerate() {
+        let title = app
+            .board
+            .get_task(*blocker_id)
+            .map(|task| task.title.as_str())
+            .unwrap_or("(not on the board)");
+        let style = if blockers_selected && !searching && idx == app.edit_state.selected_blocker {
+            highlight
+        } else {
+            Style::default().fg(Color::Magenta)
+        };
+        blocker_spans.push(Span::styled(format!("⊘ {}", title), style));
+        blocker_spans.push(Span::raw("  "));
+    }
+    if blocker_spans.is_empty() {
+        blocker_spans.push(Span::styled("No blockers", Style::default().fg(Color::DarkGray)));
+    }
+    let mut candidate_lines = Vec::new();
+    if searching {
+        let candidates = app.blocker_candidates();
+        if candidates.is_empty() {
+            candidate_lines.push(Line::from(Span::styled("  no matching task", Style::default().fg(Color::DarkGray))));
+        }
+        for (idx, task) in candidates.iter().enumerate() {
+            let style = if idx == app.edit_state.selected_candidate { highlight } else { Style::default() };
+            candidate_lines.push(Line::from(Span::styled(format!("  {}", task.title), style)));
+        }
+    }
+    let blockers_title = "Blocked by (type to search, Enter: add, Alt-↑/↓: select, Ctrl-D: remove)";
+    let blockers_block = Block::default()
+        .title(if blockers_selected { format!("{} [SELECTED]", blockers_title) } else { blockers_title.to_string() })
+        .borders(Borders::ALL)
+        .style(blockers_border_style);
+    let blockers_inner = blockers_block.inner(input_chunks[8]);
+    f.render_widget(blockers_block, input_chunks[8]);
+    let [current_area, query_area, candidates_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)]).areas(blockers_inner);
+    f.render_widget(Paragraph::new(Line::from(blocker_spans)), current_area);
+    render_inline_input(f, query_area, "? ", &app.edit_state.blocker_query, blockers_selected);
+    let candidates_scroll = app
+        .edit_state
+        .selected_candidate
+        .saturating_sub(candidates_area.height.saturating_sub(1) as usize);
+    f.render_widget(Paragraph::new(candidate_lines).scroll((candidates_scroll as u16, 0)), candidates_area);
+}
+
+/// One-row input inside a larger box, such as the new checklist item.
+fn render_inline_input(f: &mut Frame, area: Rect, prefix: &str, editor: &TextEditor, focused: bool) {
+    let style = if focused { Style::default().fg(Color::Yellow) } else { Style::default().fg(Color::DarkGray) };
+    let [prefix_area, input_area] =
+        Layout::horizontal([Constraint::Length(prefix.width() as u16), Constraint::Min(0)]).areas(area);
+    f.render_widget(Paragraph::new(Span::styled(prefix, style)), prefix_area);
+    editor.render(f, input_area, Block::default(), style, focused);
+}
+
+fn render_board_picker(f: &mut Frame, app: &App) {
+    let popup_area = centered_rect(50, 60, f.area());
+    f.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = app
+        .boards
+        .iter()
+        .enumerate()
+        .map(|(idx, summary)| {
+            let is_current = summary.id == app.board.id;
+            let marker = if is_current { "● " } else { "  " };
+            let content = format!("{}{} ({} tasks)", marker, summary.title, summary.task_count);
+
+            let style = if idx == app.selected_board {
+                Style::default()
+                    .bg(Color::Blue)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            ListItem::new(Line::from(Span::styled(content, style)))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .title("Boards")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(list, popup_area);
+}
+
+fn render_filter_panel(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let filter = &app.filter;
+    let checkbox = |on: bool| if on { "[x]" } else { "[ ]" };
+    let heading = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from(Span::styled("Priority", heading))];
+    for (key, priority) in [
+        ('c', Priority::Critical),
+        ('h', Priority::High),
+        ('m', Priority::Medium),
+        ('l', Priority::Low),
+    ] {
+        let on = filter.priorities.contains(&priority);
+        lines.push(Line::from(format!("  {} {} {}", key, checkbox(on), priority)));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Due date (d)", heading)));
+    let due = [
+        None,
+        Some(DueFilter::Overdue),
+        Some(DueFilter::Today),
+        Some(DueFilter::ThisWeek),
+        Some(DueFilter::NoDueDate),
+    ];
+    let due_spans: Vec<Span> = due
+        .iter()
+        .map(|option| {
+            let text = option.map(|due| due.to_string()).unwrap_or_else(|| "any".to_string());
+            let style = if *option == filter.due {
+                Style::default().fg(Color::Black).bg(Color::Magenta)
+            } else {
+                Style::default()
+            };
+            Span::styled(format!(" {} ", text), style)
+        })
+        .collect();
+    lines.push(Line::from([vec![Span::raw(" ")], due_spans].concat()));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Label (t; C color, R rename, T delete)", heading)));
+    let mut label_spans = vec![Span::raw("  ")];
+    if filter.labels.is_empty() {
+        label_spans.push(Span::raw("any"));
+    }
+    for label in filter.labels.iter().filter_map(|id| app.board.get_label(*id)) {
+        label_spans.push(label_chip(label));
+    }
+    lines.push(Line::from(label_spans));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!(
+        "  e {} no description",
+        checkbox(filter.no_description)
+    )));
+    let cursor = if app.filter_text_editing { "▏" } else { "" };
+    lines.push(Line::from(format!("  / text: {}{}", filter.text, cursor)));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Saved views (v, s=save, D=delete)", heading)));
+    if app.board.views.is_empty() {
+        lines.push(Line::from("  none"));
+    }
+    for view in &app.board.views {
+        let is_active = Some(view.id) == app.active_view;
+        let marker = if is_active { "● " } else { "  " };
+        let style = if is_active {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(format!("{}{}", marker, view.name), style)));
+    }
+
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .title("Filter")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(panel, area);
+}
+
+/// Full-screen, read-only view of the selected task in place of the board.
+fn render_task_detail(f: &mut Frame, area: Rect, app: &mut App) {
+    let view: &App = app;
+    let Some(task) = view.get_selected_task_id().and_then(|id| view.board.get_task(id)) else {
+        return;
+    };
+
+    let heading = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let field = Style::default().fg(Color::Gray);
+    let timestamp = |at: DateTime<Utc>| at.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string();
+
+    let mut lines = vec![
+        Line::from(Span::styled(task.title.clone(), Style::default().add_modifier(Modifier::BOLD))),
+        Line::from(""),
+    ];
+    if let Some(column) = view.board.columns.get(view.selected_column) {
+        lines.push(Line::from(vec![Span::styled("Column:     ", field), Span::raw(column.title.clone())]));
+    }
+    lines.push(Line::from(vec![Span::styled("Priority:   ", field), Span::raw(task.priority.to_string())]));
+    lines.push(Line::from(vec![
+        Span::styled("Due:        ", field),
+        Span::raw(
+            task.due_date
+                .map(|date| describe_due_date(date.date_naive(), view.today))
+                .unwrap_or_else(|| "none".to_string()),
+        ),
+    ]));
+    let labels: Vec<Span> = task
+        .labels
+        .iter()
+        .filter_map(|id| view.board.get_label(*id))
+        .flat_map(|label| [label_chip(label), Span::raw(" ")])
+        .collect();
+    if !labels.is_empty() {
+        lines.push(Line::from([vec![Span::styled("Labels:     ", field)], labels].concat()));
+    }
+    if !task.blocked_by.is_empty() {
+        let open = view.board.open_blockers(task);
+        let mut spans = vec![Span::styled("Blocked by: ", field)];
+        for blocker in task.blocked_by.iter().filter_map(|id| view.board.get_task(*id)) {
+            let style = if open.iter().any(|open| open.id == blocker.id) {
+                Style::default().fg(Color::Magenta)
+            } else {
+                Style::default().fg(Color::Green)
+            };
+            spans.push(Span::styled(blocker.title.clone(), style));
+            spans.push(Span::raw(" "));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(vec![Span::styled("Created:    ", field), Span::raw(timestamp(task.created_at))]));
+    lines.push(Line::from(vec![Span::styled("Updated:    ", field), Span::raw(timestamp(task.updated_at))]));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Description", heading)));
+    match task.description.as_deref().filter(|desc| !desc.trim().is_empty()) {
+        Some(description) => lines.extend(markdown::render(description).lines),
+        None => lines.push(Line::from(Span::styled("No description", Style::default().fg(Color::DarkGray)))),
+    }
+    if let Some((done, total)) = task.checklist_progress() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!("Checklist {}/{}", done, total), heading)));
+        lines.extend(task.checklist.iter().map(|item| {
+            Line::from(format!("  {} {}", if item.done { "[x]" } else { "[ ]" }, item.text))
+        }));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Activity", heading)));
+    if view.detail_events.is_empty() {
+        lines.push(Line::from("  No recorded activity"));
+    }
+    for event in &view.detail_events {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}  ", timestamp(event.at)), Style::default().fg(Color::Gray)),
+            Span::raw(describe_event(view, event)),
+        ]));
+    }
+
+    let detail = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let visible = area.height.saturating_sub(2);
+    let total = detail.line_count(area.width.saturating_sub(2)) as u16;
+    let scroll = view.detail_scroll.min(total.saturating_sub(visible));
+
+    let mut block = Block::default()
+        .title("Task Details")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(Color::Cyan));
+    if total > visible {
+        block = block.title_bottom(
+            Line::from(format!(" {}-{} of {} ", scroll + 1, (scroll + visible).min(total), total)).right_aligned(),
+        );
+    }
+    f.render_widget(Clear, area);
+    f.render_widget(detail.scroll((scroll, 0)).block(block), area);
+
+    app.detail_scroll = scroll;
+}
+
+/// Timeline text for an event, e.g. `moved To Do → In Progress`.
+fn describe_event(app: &App, event: &TaskEvent) -> String {
+    let column_title = |id| {
+        app.board
+            .columns
+            .iter()
+            .find(|column| column.id == id)
+            .map(|column| column.title.clone())
+            .unwrap_or_else(|| "a deleted column".to_string())
+    };
+    let quoted = |value: &str| {
+        if value.is_empty() {
+            "(none)".to_string()
+        } else {
+            format!("\"{}\"", value)
+        }
+    };
+
+    match &event.kind {
+        EventKind::Created => "created".to_string(),
+        EventKind::Changed { field, old, new } => {
+            format!("changed {} from {} to {}", field, quoted(old), quoted(new))
+        }
+        EventKind::Moved { from, to } => {
+            format!("moved {} → {}", column_title(*from), column_title(*to))
+        }
+        EventKind::Archived => "archived".to_string(),
+        EventKind::Deleted => "moved to trash".to_string(),
+        EventKind::Restored => "restored".to_string(),
+    }
+}
+
+fn render_metrics(f: &mut Frame, app: &App) {
+    let popup_area = centered_rect(70, 80, f.area());
+    f.render_widget(Clear, popup_area);
+
+    let metrics = &app.metrics;
+    let heading = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let average = |duration: Option<chrono::Duration>| {
+        duration.map(format_duration).unwrap_or_else(|| "n/a".to_string())
+    };
+
+    let mut lines = vec![
+        Line::from(format!("Completed tasks: {}", metrics.completed)),
+        Line::from(format!("Average lead time (created → done): {}", average(metrics.lead_time))),
+        Line::from(format!(
+            "Average cycle time (started → done): {}",
+            average(metrics.cycle_time)
+        )),
+        Line::from(""),
+        Line::from(Span::styled("Throughput per week", heading)),
+    ];
+
+    let max = metrics
+        .weekly_throughput
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for (week, count) in &metrics.weekly_throughput {
+        let bar = "█".repeat(count * 30 / max);
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {}  ", week.format("%m/%d"))),
+            Span::styled(bar, Style::default().fg(Color::Green)),
+            Span::raw(format!(" {}", count)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Average time per column", heading)));
+    if metrics.column_times.is_empty() {
+        lines.push(Line::from("  No moves recorded yet"));
+    }
+    for column in &metrics.column_times {
+        lines.push(Line::from(format!(
+            "  {:<20} {:>8}  ({} visits)",
+            column.title,
+            format_duration(column.average),
+            column.visits
+        )));
+    }
+
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .title("Flow Metrics")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(panel, popup_area);
+}
+
+fn render_archive(f: &mut Frame, app: &App) {
+    let popup_area = centered_rect(70, 70, f.area());
+    f.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = if app.archive.is_empty() {
+        vec![ListItem::new("Nothing archived or deleted")]
+    } else {
+        app.archive
+            .iter()
+            .enumerate()
+            .map(|(idx, hidden)| {
+                let state = hidden
+                    .task
+                    .hidden_state()
+                    .map(|state| state.to_string())
+                    .unwrap_or_default();
+                let when = hidden
+                    .task
+                    .hidden_at()
+                    .map(|date| date.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                let column = app
+                    .board
+                    .columns
+                    .iter()
+                    .find(|column| column.id == hidden.column_id)
+                    .map(|column| column.title.as_str())
+                    .unwrap_or("?");
+
+                let style = if idx == app.selected_archived {
+                    Style::default()
+                        .bg(Color::Blue)
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let state_style = match hidden.task.hidden_state() {
+                    Some(HiddenState::Deleted) => style.fg(Color::Red),
+                    _ => style.fg(Color::Gray),
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<9}", state), state_style),
+                    Span::styled(format!("{}  ", when), style),
+                    Span::styled(hidden.task.title.clone(), style),
+                    Span::styled(format!("  (from {})", column), style),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .title("Archive & Trash")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(list, popup_area);
+}
+
+fn render_name_popup(f: &mut Frame, title: &str, input: &str) {
+    let area = centered_rect(40, 20, f.area());
+    let popup_area = Rect {
+        height: area.height.min(3),
+        ..area
+    };
+    f.render_widget(Clear, popup_area);
+
+    let input = Paragraph::new(input)
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Green)),
+        );
+    f.render_widget(input, popup_area);
+}
+
+fn render_delete_column_popup(f: &mut Frame, app: &App) {
+    let Some(column) = app.board.columns.get(app.selected_column) else {
+        return;
+    };
+    let target = app.column_delete_target().and_then(|idx| app.board.columns.get(idx));
+    let move_line = match target {
+        Some(target) if app.board.are_moves_blocked(target.id, column.tasks.len()) => {
+            format!("m: move them to \"{}\" (blocked by its WIP limit)", target.title)
+        }
+        Some(target) => format!("m: move them to \"{}\"", target.title),
+        None => "m: move them".to_string(),
+    };
+
+    let area = centered_rect(50, 30, f.area());
+    let popup_area = Rect {
+        height: area.height.min(6),
+        ..area
+    };
+    f.render_widget(Clear, popup_area);
+
+    let text = vec![
+        Line::from(format!("Column \"{}\" contains {} tasks.", column.title, column.tasks.len())),
+        Line::from(""),
+        Line::from(move_line),
+        Line::from("x: move them to the trash    Esc: cancel"),
+    ];
+    let popup = Paragraph::new(text).block(
+        Block::default()
+            .title("Delete Column")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Red)),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+    use crate::models::{Board, Task, search};
+
+    fn highlighted(text: &str, query: &str) -> Vec<String> {
+        highlight_matches(text, query, Style::default())
+            .into_iter()
+            .filter(|span| span.style == search_match_style())
+            .map(|span| span.content.into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn matches_ignore_case() {
+        assert_eq!(highlighted("Fix Login, then login again", "LOGIN"), ["Login", "login"]);
+    }
+
+    #[test]
+    fn chars_that_change_length_when_lowercased_do_not_panic() {
+        // "\u{212A}" (Kelvin sign) lowercases to one byte, "İ" to three
+        let text = "\u{212A}İİ";
+        // No highlight and no search hit either: both use the same rule
+        assert!(highlighted(text, "i").is_empty());
+        assert!(!search::matches(text, "i"));
+        assert_eq!(highlighted(text, "k"), ["\u{212A}"]);
+        let spans = highlight_matches(text, "k", Style::default());
+        assert_eq!(spans.iter().map(|span| span.content.as_ref()).collect::<String>(), text);
+    }
+
+    #[test]
+    fn empty_query_keeps_the_text() {
+        let spans = highlight_matches("title", "", Style::default());
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "title");
+    }
+
+    fn scrolled(offset: usize, selected: Option<usize>, len: usize, height: usize) -> usize {
+        let mut state = ListState::default().with_offset(offset);
+        scroll_into_view(&mut state, selected, len, height);
+        state.offset()
+    }
+
+    #[test]
+    fn scrolling_shows_the_selection_with_the_least_movement() {
+        // Already in view: nothing moves
+        assert_eq!(scrolled(2, Some(4), 10, 5), 2);
+        // At the top and bottom of the list
+        assert_eq!(scrolled(4, Some(0), 10, 5), 0);
+        assert_eq!(scrolled(0, Some(9), 10, 5), 5);
+        // Just past either edge of the view
+        assert_eq!(scrolled(3, Some(2), 10, 5), 2);
+        assert_eq!(scrolled(3, Some(8), 10, 5), 4);
+        // Without a selection in the list, it shows from the top
+        assert_eq!(scrolled(3, Some(10), 10, 5), 0);
+        assert_eq!(scrolled(3, None, 10, 5), 0);
+    }
+
+    #[test]
+    fn resizing_the_view_keeps_the_selection_and_fills_it() {
+        // Shrinking scrolls down to keep the bottom card
+        assert_eq!(scrolled(5, Some(9), 10, 3), 7);
+        // Growing scrolls back up rather than leave empty rows
+        assert_eq!(scrolled(7, Some(9), 10, 8), 2);
+        assert_eq!(scrolled(7, Some(9), 10, 20), 0);
+        // No rows at all still keeps the offset on the selection
+        assert_eq!(scrolled(0, Some(3), 10, 0), 3);
+    }
+
+    /// Renders the board into a `width` x `height` buffer, one string per row.
+    fn render(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| render_board(f, f.area(), app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    fn app_with_cards(count: usize) -> App {
+        let mut app = App::new();
+        app.board = Board::new("Test".to_string());
+        let column_id = app.board.columns[0].id;
+        for i in 0..count {
+            app.board.add_task(column_id, Task::new(format!("card {}", i)));
+        }
+        app
+    }
+
+    #[test]
+    fn scroll_indicators_count_the_cards_out_of_view() {
+        // Ten rows leave eight for cards
+        let mut app = app_with_cards(12);
+        let bottom_border = |rows: &[String]| rows[9].clone();
+
+        let rows = render(&mut app, 72, 10);
+        assert!(!bottom_border(&rows).contains('▲'));
+        assert!(bottom_border(&rows).contains("▼ 4"));
+
+        app.selected_task = 11;
+        let rows = render(&mut app, 72, 10);
+        assert!(bottom_border(&rows).contains("▲ 4"));
+        assert!(!bottom_border(&rows).contains('▼'));
+
+        app.selected_task = 2;
+        let rows = render(&mut app, 72, 10);
+        assert!(bottom_border(&rows).contains("▲ 2"));
+        assert!(bottom_border(&rows).contains("▼ 2"));
+
+        // A shorter terminal scrolls to keep the selection
+        app.selected_task = 11;
+        let rows = render(&mut app, 72, 6);
+        assert!(rows[5].contains("▲ 8"));
+        assert!(rows.iter().any(|row| row.contains("card 11")));
+    }
+
+    #[test]
+    fn narrow_terminals_page_the_columns() {
+        let mut app = app_with_cards(1);
+
+        // Three columns fit exactly at the minimum width
+        let rows = render(&mut app, MIN_COLUMN_WIDTH * 3, 6);
+        assert!(!rows[0].contains('◀') && !rows[0].contains('▶'));
+        assert!(app.column_areas.iter().all(|area| area.width == MIN_COLUMN_WIDTH));
+
+        // One cell less and only two fit
+        let rows = render(&mut app, MIN_COLUMN_WIDTH * 3 - 1, 6);
+        assert!(rows[0].contains("1 ▶") && !rows[0].contains('◀'));
+        assert_eq!(app.column_areas[2], Rect::default());
+
+        app.selected_column = 2;
+        let rows = render(&mut app, MIN_COLUMN_WIDTH * 3 - 1, 6);
+        assert!(rows[0].contains("◀ 2") && !rows[0].contains('▶'));
+        assert_eq!(app.column_areas[0], Rect::default());
+        assert_eq!(app.column_areas[2].x, 0);
+
+        // Short of two columns, one is shown at a time
+        app.selected_column = 1;
+        let rows = render(&mut app, MIN_COLUMN_WIDTH * 2 - 1, 6);
+        assert!(rows[0].contains("◀ 1") && rows[0].contains("1 ▶"));
+        assert_eq!(app.column_areas[1], Rect::new(0, 0, MIN_COLUMN_WIDTH * 2 - 1, 6));
+
+        // Below the minimum a single column still shows
+        render(&mut app, MIN_COLUMN_WIDTH - 1, 6);
+        assert_eq!(app.column_areas[1], Rect::new(0, 0, MIN_COLUMN_WIDTH - 1, 6));
+    }
+}
@@ -0,0 +1,405 @@
+use std::ops::Range;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthChar;
+
+/// Editable text with a cursor and readline-style key bindings, used by the
+/// input fields of the task popup. Rows are measured in terminal columns, so
+/// wide characters such as CJK take two cells.
+#[derive(Clone, Debug, Default)]
+pub struct TextEditor {
+    text: String,
+    /// Byte offset into `text`, always on a char boundary.
+    cursor: usize,
+    /// Whether the text may contain newlines.
+    multiline: bool,
+}
+
+impl TextEditor {
+    /// A single-line editor with the cursor at the end of `text`.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into().replace('\n', " ");
+        Self {
+            cursor: text.len(),
+            text,
+            multiline: false,
+        }
+    }
+
+    pub fn multiline(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            cursor: text.len(),
+            text,
+            multiline: true,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        if c == '\n' && !self.multiline {
+            return;
+        }
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Inserts pasted text at the cursor. Line breaks become spaces in a
+    /// single-line editor.
+    pub fn insert_str(&mut self, s: &str) {
+        let s = s.replace("\r\n", "\n").replace('\r', "\n");
+        let s = if self.multiline { s } else { s.replace('\n', " ") };
+        self.text.insert_str(self.cursor, &s);
+        self.cursor += s.len();
+    }
+
+    pub fn delete_before(&mut self) {
+        if let Some(start) = self.prev_boundary() {
+            self.text.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+
+    pub fn delete_after(&mut self) {
+        if let Some(end) = self.next_boundary() {
+            self.text.replace_range(self.cursor..end, "");
+        }
+    }
+
+    pub fn delete_word_before(&mut self) {
+        let start = self.word_start();
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    pub fn delete_word_after(&mut self) {
+        let end = self.word_end();
+        self.text.replace_range(self.cursor..end, "");
+    }
+
+    pub fn kill_to_line_start(&mut self) {
+        let start = self.line_start();
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    pub fn kill_to_line_end(&mut self) {
+        let end = self.line_end();
+        self.text.replace_range(self.cursor..end, "");
+    }
+
+    pub fn move_left(&mut self) {
+        if let Some(start) = self.prev_boundary() {
+            self.cursor = start;
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if let Some(end) = self.next_boundary() {
+            self.cursor = end;
+        }
+    }
+
+    pub fn move_word_left(&mut self) {
+        self.cursor = self.word_start();
+    }
+
+    pub fn move_word_right(&mut self) {
+        self.cursor = self.word_end();
+    }
+
+    pub fn move_line_start(&mut self) {
+        self.cursor = self.line_start();
+    }
+
+    pub fn move_line_end(&mut self) {
+        self.cursor = self.line_end();
+    }
+
+    /// Moves to the same column of the previous line. Returns false on the
+    /// first line so the caller can move focus instead.
+    pub fn move_up(&mut self) -> bool {
+        let start = self.line_start();
+        if start == 0 {
+            return false;
+        }
+        let column = display_width(&self.text[start..self.cursor]);
+        let prev_start = self.text[..start - 1].rfind('\n').map_or(0, |i| i + 1);
+        self.cursor = offset_at_column(&self.text, prev_start..start - 1, column);
+        true
+    }
+
+    /// Moves to the same column of the next line. Returns false on the last
+    /// line.
+    pub fn move_down(&mut self) -> bool {
+        let end = self.line_end();
+        if end == self.text.len() {
+            return false;
+        }
+        let column = display_width(&self.text[self.line_start()..self.cursor]);
+        let next_start = end + 1;
+        let next_end = self.text[next_start..].find('\n').map_or(self.text.len(), |i| next_start + i);
+        self.cursor = offset_at_column(&self.text, next_start..next_end, column);
+        true
+    }
+
+    /// Applies the readline-style editing keys. Returns false for keys the
+    /// editor doesn't handle, such as Enter, Tab and Up/Down.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.move_line_start(),
+            KeyCode::Char('e') if ctrl => self.move_line_end(),
+            KeyCode::Char('b') if ctrl => self.move_left(),
+            KeyCode::Char('f') if ctrl => self.move_right(),
+            KeyCode::Char('h') if ctrl => self.delete_before(),
+            KeyCode::Char('d') if ctrl => self.delete_after(),
+            KeyCode::Char('w') if ctrl => self.delete_word_before(),
+            KeyCode::Char('u') if ctrl => self.kill_to_line_start(),
+            KeyCode::Char('k') if ctrl => self.kill_to_line_end(),
+            KeyCode::Char('b') if alt => self.move_word_left(),
+            KeyCode::Char('f') if alt => self.move_word_right(),
+            KeyCode::Char('d') if alt => self.delete_word_after(),
+            KeyCode::Char(_) if ctrl || alt => return false,
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Backspace if alt || ctrl => self.delete_word_before(),
+            KeyCode::Backspace => self.delete_before(),
+            KeyCode::Delete => self.delete_after(),
+            KeyCode::Left if alt || ctrl => self.move_word_left(),
+            KeyCode::Right if alt || ctrl => self.move_word_right(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.move_line_start(),
+            KeyCode::End => self.move_line_end(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Draws the text inside `block`, wrapped at the area's width and
+    /// scrolled so the cursor row is visible. The terminal cursor is only
+    /// placed when `focused`.
+    pub fn render(&self, f: &mut Frame, area: Rect, block: Block, style: Style, focused: bool) {
+        let inner = block.inner(area);
+        let (rows, (cursor_row, cursor_col)) = self.layout(inner.width);
+        let scroll = cursor_row.saturating_sub(inner.height.saturating_sub(1) as usize);
+
+        let lines: Vec<Line> = rows
+            .iter()
+            .skip(scroll)
+            .map(|row| Line::from(&self.text[row.clone()]))
+            .collect();
+        f.render_widget(Paragraph::new(lines).style(style).block(block), area);
+
+        if focused && inner.width > 0 && inner.height > 0 {
+            f.set_cursor_position(Position::new(
+                inner.x + cursor_col as u16,
+                inner.y + (cursor_row - scroll) as u16,
+            ));
+        }
+    }
+
+    /// Breaks the text into rows of at most `width` columns and finds the
+    /// cursor's row and column. A wide character that doesn't fit at the
+    /// end of a row moves to the next one.
+    fn layout(&self, width: u16) -> (Vec<Range<usize>>, (usize, usize)) {
+        let width = width.max(1) as usize;
+        let mut rows = Vec::new();
+        let mut cursor = (0, 0);
+        let mut line_start = 0;
+
+        for line in self.text.split('\n') {
+            let line_end = line_start + line.len();
+            let mut row_start = line_start;
+            let mut column = 0;
+
+            for (i, c) in line.char_indices() {
+                let offset = line_start + i;
+                let char_width = c.width().unwrap_or(0);
+                if column + char_width > width && column > 0 {
+                    rows.push(row_start..offset);
+                    row_start = offset;
+                    column = 0;
+                }
+                if offset == self.cursor {
+                    cursor = (rows.len(), column);
+                }
+                column += char_width;
+            }
+
+            if self.cursor == line_end {
+                cursor = (rows.len(), column);
+            }
+            rows.push(row_start..line_end);
+            // A cursor right after a full row sits at the start of the next.
+            if self.cursor == line_end && column >= width {
+                cursor = (rows.len(), 0);
+                rows.push(line_end..line_end);
+            }
+            line_start = line_end + 1;
+        }
+
+        (rows, cursor)
+    }
+
+    fn prev_boundary(&self) -> Option<usize> {
+        self.text[..self.cursor].char_indices().next_back().map(|(i, _)| i)
+    }
+
+    fn next_boundary(&self) -> Option<usize> {
+        self.text[self.cursor..].chars().next().map(|c| self.cursor + c.len_utf8())
+    }
+
+    fn line_start(&self) -> usize {
+        self.text[..self.cursor].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self) -> usize {
+        self.text[self.cursor..].find('\n').map_or(self.text.len(), |i| self.cursor + i)
+    }
+
+    /// Start of the word before the cursor, skipping separators first.
+    fn word_start(&self) -> usize {
+        let before = &self.text[..self.cursor];
+        let mut chars = before.char_indices().rev().peekable();
+        while chars.next_if(|(_, c)| !c.is_alphanumeric()).is_some() {}
+        while chars.next_if(|(_, c)| c.is_alphanumeric()).is_some() {}
+        chars.peek().map_or(0, |(i, c)| i + c.len_utf8())
+    }
+
+    /// End of the word after the cursor, skipping separators first.
+    fn word_end(&self) -> usize {
+        let after = &self.text[self.cursor..];
+        let mut chars = after.char_indices().peekable();
+        while chars.next_if(|(_, c)| !c.is_alphanumeric()).is_some() {}
+        while chars.next_if(|(_, c)| c.is_alphanumeric()).is_some() {}
+        self.cursor + chars.peek().map_or(after.len(), |(i, _)| *i)
+    }
+}
+
+fn display_width(s: &str) -> usize {
+    s.chars().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// Byte offset within `range` of `text` that is closest to `column` without
+/// passing it.
+fn offset_at_column(text: &str, range: Range<usize>, column: usize) -> usize {
+    let mut width = 0;
+    for (i, c) in text[range.clone()].char_indices() {
+        width += c.width().unwrap_or(0);
+        if width > column {
+            return range.start + i;
+        }
+    }
+    range.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor_at(text: &str, cursor: usize) -> TextEditor {
+        let mut editor = TextEditor::multiline(text);
+        editor.cursor = cursor;
+        editor
+    }
+
+    fn rows(editor: &TextEditor, width: u16) -> Vec<&str> {
+        editor.layout(width).0.into_iter().map(|row| &editor.text[row]).collect()
+    }
+
+    #[test]
+    fn wide_chars_that_do_not_fit_wrap_to_the_next_row() {
+        let editor = TextEditor::multiline("ab漢字");
+        assert_eq!(rows(&editor, 3), ["ab", "漢", "字"]);
+        assert_eq!(editor.layout(3).1, (2, 2));
+    }
+
+    #[test]
+    fn cursor_before_a_wrapped_wide_char_starts_the_next_row() {
+        let editor = editor_at("漢字漢", "漢字".len());
+        assert_eq!(rows(&editor, 4), ["漢字", "漢"]);
+        assert_eq!(editor.layout(4).1, (1, 0));
+    }
+
+    #[test]
+    fn cursor_after_a_full_row_gets_a_row_of_its_own() {
+        let editor = TextEditor::multiline("abcd");
+        assert_eq!(rows(&editor, 4), ["abcd", ""]);
+        assert_eq!(editor.layout(4).1, (1, 0));
+    }
+
+    #[test]
+    fn vertical_moves_keep_the_display_column() {
+        let mut editor = editor_at("漢字x\nabcde", "漢".len());
+        assert!(editor.move_down());
+        assert_eq!(editor.cursor, "漢字x\nab".len());
+        assert!(!editor.move_down());
+
+        assert!(editor.move_up());
+        assert_eq!(editor.cursor, "漢".len());
+        assert!(!editor.move_up());
+    }
+
+    #[test]
+    fn vertical_moves_stop_before_a_wide_char_they_would_split() {
+        let mut editor = editor_at("漢字x\nabcde", "漢字x\nabc".len());
+        assert!(editor.move_up());
+        assert_eq!(editor.cursor, "漢".len());
+
+        let mut editor = editor_at("ab\n漢字", 1);
+        assert!(editor.move_down());
+        assert_eq!(editor.cursor, "ab\n".len());
+    }
+
+    #[test]
+    fn vertical_moves_clamp_to_shorter_lines() {
+        let mut editor = editor_at("a\nlonger", "a\nlonger".len());
+        assert!(editor.move_up());
+        assert_eq!(editor.cursor, 1);
+    }
+
+    #[test]
+    fn word_boundaries_skip_separators_first() {
+        let text = "foo, bar-baz";
+        assert_eq!(editor_at(text, text.len()).word_start(), "foo, bar-".len());
+        assert_eq!(editor_at(text, "foo, ".len()).word_start(), 0);
+        assert_eq!(editor_at(text, "foo".len()).word_end(), "foo, bar".len());
+        assert_eq!(editor_at(text, "foo, bar".len()).word_end(), text.len());
+        assert_eq!(editor_at("naïve 東京", 0).word_end(), "naïve".len());
+        assert_eq!(editor_at("naïve 東京", "naïve 東京".len()).word_start(), "naïve ".len());
+    }
+
+    #[test]
+    fn pasted_line_endings_are_normalised() {
+        let mut editor = TextEditor::multiline("a");
+        editor.insert_str("b\r\nc\rd");
+        assert_eq!(editor.text(), "ab\nc\nd");
+        assert_eq!(editor.cursor, editor.text().len());
+
+        let mut editor = TextEditor::new("a");
+        editor.insert_str("b\r\nc");
+        assert_eq!(editor.text(), "ab c");
+    }
+}